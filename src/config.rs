@@ -4,6 +4,93 @@ use serde::Deserialize;
 #[derive(Deserialize, Default)]
 pub struct Config {
     pub auto_execute: Option<bool>,
+    pub service: Option<ServiceConfig>,
+    pub sampling: Option<SamplingConfig>,
+}
+
+impl Config {
+    /// Resolves the `[sampling]` section (if present) into a complete
+    /// `SamplingParams`, falling back to `SamplingParams::default()` for any
+    /// field left unset.
+    pub fn sampling_params(&self) -> crate::model::SamplingParams {
+        self.sampling.clone().unwrap_or_default().to_params()
+    }
+}
+
+/// Overrides for the `[service]` section. The systemd backend honors every
+/// field; launchd honors only `unit_name` (as its label) and `status_args`;
+/// OpenRC and FreeBSD rc.d honor only `status_args`. Argument templates may
+/// use the `{unit}` placeholder, which is substituted with `unit_name` (or
+/// its default) before the command runs; OpenRC and rc.d always substitute
+/// `shitd`.
+#[derive(Deserialize, Default, Clone)]
+pub struct ServiceConfig {
+    /// Override the service manager binary (systemd only), e.g. a
+    /// non-standard `systemctl` path or a wrapper script.
+    pub manager_bin: Option<String>,
+    /// Run system-wide instead of the default per-user scope (systemd only;
+    /// drops `--user` from the built-in argument templates).
+    pub system_scope: Option<bool>,
+    /// Override the unit/service name (default: `shitd`, or `dev.ava.shitd`
+    /// for launchd). Not used by OpenRC or FreeBSD rc.d.
+    pub unit_name: Option<String>,
+    /// Argument template used to enable the service at boot/login (systemd
+    /// only).
+    pub enable_args: Option<Vec<String>>,
+    /// Argument template used to start the service (systemd only).
+    pub start_args: Option<Vec<String>>,
+    /// Argument template used to stop the service (systemd only).
+    pub stop_args: Option<Vec<String>>,
+    /// Argument template used to query the service's status.
+    pub status_args: Option<Vec<String>>,
+    /// Argument template used to fetch the service's logs (systemd only). Separate from
+    /// `status_args` since the log viewer (`journalctl` on systemd) isn't
+    /// the same command as a status query.
+    pub logs_args: Option<Vec<String>>,
+}
+
+/// Overrides for the `[sampling]` section, controlling how the model
+/// samples each token, how many tokens a completion may run to, and how
+/// many independent completions to draw. Unset fields fall back to the
+/// greedy-decoding defaults in `model::SamplingParams`.
+#[derive(Deserialize, Default, Clone)]
+pub struct SamplingConfig {
+    /// Sampling temperature; unset means greedy (argmax) decoding.
+    pub temperature: Option<f64>,
+    /// Restrict sampling to the top K most likely tokens.
+    pub top_k: Option<usize>,
+    /// Restrict sampling to the smallest set of tokens whose cumulative
+    /// probability exceeds this nucleus threshold.
+    pub top_p: Option<f64>,
+    /// Penalize each of the last `repeat_last_n` tokens (prompt plus
+    /// generated) to discourage repetition: a positive logit is divided by
+    /// this factor, a negative one multiplied. `1.0` (the default) is a no-op.
+    pub repeat_penalty: Option<f32>,
+    /// How many trailing tokens count as "recent" for `repeat_penalty`.
+    pub repeat_last_n: Option<usize>,
+    /// RNG seed for non-greedy sampling.
+    pub seed: Option<u64>,
+    /// Maximum tokens generated per completion after the first sampled token
+    /// (so at most `max_tokens + 1` in all).
+    pub max_tokens: Option<usize>,
+    /// Number of independent completions to draw per request.
+    pub num_samples: Option<usize>,
+}
+
+impl SamplingConfig {
+    fn to_params(&self) -> crate::model::SamplingParams {
+        let defaults = crate::model::SamplingParams::default();
+        crate::model::SamplingParams {
+            temperature: self.temperature.or(defaults.temperature),
+            top_k: self.top_k.or(defaults.top_k),
+            top_p: self.top_p.or(defaults.top_p),
+            repeat_penalty: self.repeat_penalty.unwrap_or(defaults.repeat_penalty),
+            repeat_last_n: self.repeat_last_n.unwrap_or(defaults.repeat_last_n),
+            seed: self.seed.unwrap_or(defaults.seed),
+            max_tokens: self.max_tokens.unwrap_or(defaults.max_tokens),
+            num_samples: self.num_samples.unwrap_or(defaults.num_samples),
+        }
+    }
 }
 
 pub fn load_config() -> Result<Config> {
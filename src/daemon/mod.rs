@@ -1,3 +1,4 @@
+mod rpc;
 pub mod server;
 pub mod service;
 
@@ -7,6 +8,7 @@ pub fn handle(action: crate::DaemonCommand) -> Result<()> {
     match action {
         crate::DaemonCommand::Run => server::run_server(),
         crate::DaemonCommand::Start => start(),
+        crate::DaemonCommand::Install => service::install(),
         crate::DaemonCommand::Stop => service::stop(),
         crate::DaemonCommand::Restart => service::restart(),
         crate::DaemonCommand::Status => status(),
@@ -30,15 +32,41 @@ fn status() -> Result<()> {
     let port_file = server::port_file_path();
     if !port_file.exists() {
         eprintln!("shitd: not running (no port file)");
+        if service::is_installed().unwrap_or(false) {
+            let _ = service::status();
+        }
         return Ok(());
     }
-    let port_str = std::fs::read_to_string(&port_file)?;
-    let port: u16 = port_str.trim().parse()?;
-    let url = format!("http://127.0.0.1:{}/health", port);
+    let (port, token) = server::read_port_file(&port_file)?;
+
+    let url = format!("http://127.0.0.1:{}/rpc", port);
+    let body = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "status"}).to_string();
     let agent = ureq::Agent::new_with_defaults();
-    match agent.get(&url).call() {
-        Ok(_) => eprintln!("shitd: running on port {}", port),
-        Err(_) => eprintln!("shitd: not responding (port file exists but server unreachable)"),
+    let rpc_result = agent
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("X-Shit-Token", &token)
+        .send(body.as_str())
+        .ok()
+        .and_then(|resp| resp.into_body().read_to_string().ok())
+        .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok());
+
+    match rpc_result {
+        Some(v) if v.get("result").is_some() => {
+            let r = &v["result"];
+            eprintln!(
+                "shitd: running on port {} (v{}, model {}..., uptime {}s, {} RPC infer requests served)",
+                port,
+                r["version"].as_str().unwrap_or("?"),
+                &r["model_sha256"].as_str().unwrap_or("?")[..7.min(r["model_sha256"].as_str().unwrap_or("").len())],
+                r["uptime_secs"].as_u64().unwrap_or(0),
+                r["requests_served"].as_u64().unwrap_or(0),
+            );
+        }
+        _ => {
+            eprintln!("shitd: not responding (port file exists but server unreachable)");
+            let _ = service::status();
+        }
     }
     Ok(())
 }
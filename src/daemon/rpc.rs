@@ -0,0 +1,119 @@
+//! JSON-RPC 2.0 control surface served at `POST /rpc`, covering daemon/model
+//! lifecycle management beyond plain inference: `infer`, `status` (model
+//! hash/version, uptime, and how many `infer` calls this endpoint has
+//! served; `/infer`, `/infer/stream` and the Unix socket are not counted),
+//! `reload` (re-run `find_model` and swap the live `Engine` without
+//! restarting the process), and `shutdown`.
+
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::model::{find_model, Engine};
+
+/// Standard JSON-RPC 2.0 error codes used by this dispatcher.
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Shared daemon state threaded into RPC method handlers.
+pub struct DaemonState {
+    engine: Arc<Mutex<Engine>>,
+    server: Arc<tiny_http::Server>,
+    started_at: Instant,
+    requests_served: AtomicU64,
+}
+
+impl DaemonState {
+    pub fn new(engine: Arc<Mutex<Engine>>, server: Arc<tiny_http::Server>) -> Self {
+        Self {
+            engine,
+            server,
+            started_at: Instant::now(),
+            requests_served: AtomicU64::new(0),
+        }
+    }
+}
+
+type MethodError = (i64, String);
+
+/// Handle one JSON-RPC request body, returning the serialized response
+/// envelope (`{"jsonrpc":"2.0","id":...,"result"|"error":...}`).
+pub fn handle(body: &str, state: &DaemonState) -> String {
+    let request: Value = match serde_json::from_str(body) {
+        Ok(v) => v,
+        Err(_) => return error_response(Value::Null, INVALID_PARAMS, "invalid JSON"),
+    };
+
+    let id = request["id"].clone();
+    let method = request["method"].as_str().unwrap_or("");
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "infer" => rpc_infer(&params, state),
+        "status" => rpc_status(state),
+        "reload" => rpc_reload(state),
+        "shutdown" => rpc_shutdown(state),
+        other => Err((METHOD_NOT_FOUND, format!("method not found: {other}"))),
+    };
+
+    match result {
+        Ok(value) => success_response(id, value),
+        Err((code, message)) => error_response(id, code, &message),
+    }
+}
+
+fn rpc_infer(params: &Value, state: &DaemonState) -> Result<Value, MethodError> {
+    let prompt = params["prompt"]
+        .as_str()
+        .ok_or((INVALID_PARAMS, "missing \"prompt\" string param".to_string()))?;
+
+    let sampling_params = crate::daemon::server::sampling_params_from_json(params);
+
+    state.requests_served.fetch_add(1, Ordering::Relaxed);
+    let mut engine = lock_engine(&state.engine);
+    let fixes = crate::model::infer_with_engine(&mut engine, prompt, &sampling_params)
+        .map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+    Ok(json!({ "fixes": fixes }))
+}
+
+fn rpc_status(state: &DaemonState) -> Result<Value, MethodError> {
+    let (version, model_sha256, tokenizer_sha256) = crate::model::model_info();
+    Ok(json!({
+        "version": version,
+        "model_sha256": model_sha256,
+        "tokenizer_sha256": tokenizer_sha256,
+        "uptime_secs": state.started_at.elapsed().as_secs(),
+        "requests_served": state.requests_served.load(Ordering::Relaxed),
+    }))
+}
+
+fn rpc_reload(state: &DaemonState) -> Result<Value, MethodError> {
+    let paths = find_model().map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+    let new_engine = Engine::new(&paths.model_path, &paths.tokenizer_path)
+        .map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+
+    *lock_engine(&state.engine) = new_engine;
+    Ok(json!({ "reloaded": true }))
+}
+
+fn rpc_shutdown(state: &DaemonState) -> Result<Value, MethodError> {
+    state.server.unblock();
+    Ok(json!({ "shutting_down": true }))
+}
+
+fn lock_engine(engine: &Mutex<Engine>) -> std::sync::MutexGuard<'_, Engine> {
+    match engine.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn success_response(id: Value, result: Value) -> String {
+    json!({"jsonrpc": "2.0", "id": id, "result": result}).to_string()
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> String {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}}).to_string()
+}
@@ -1,11 +1,18 @@
 use anyhow::Result;
 use log::{error, info, warn};
+use std::collections::VecDeque;
+use std::io::Read as _;
 use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::Duration;
 
-use crate::model::{find_model, Engine};
+use crate::daemon::rpc::DaemonState;
+use crate::model::{find_model, Engine, SamplingParams};
 
-/// Returns the path where the daemon writes its port number.
+const TOKEN_HEADER: &str = "X-Shit-Token";
+
+/// Returns the path where the daemon writes its port number (and, on the
+/// same line, its capability token separated by a space).
 /// Linux: $XDG_RUNTIME_DIR/shitd.port
 /// macOS: ~/Library/Application Support/shit/shitd.port
 /// Fallback: /tmp/shitd-$USER.port
@@ -22,9 +29,104 @@ pub fn port_file_path() -> PathBuf {
     PathBuf::from(format!("/tmp/shitd-{}.port", user))
 }
 
+/// Returns the path of the Unix-domain-socket gateway, which is preferred
+/// over the TCP listener on Linux/macOS since access is gated by filesystem
+/// permissions rather than anyone who can read a port number.
+/// Linux: $XDG_RUNTIME_DIR/shitd.sock
+/// macOS: ~/Library/Application Support/shit/shitd.sock
+/// Fallback: /tmp/shitd-$USER.sock
+pub fn socket_path() -> PathBuf {
+    port_file_path().with_extension("sock")
+}
+
+/// Returns the path of the daemon's own rotating log file, used as a
+/// fallback by the `logs` command on platforms with no native log viewer
+/// (OpenRC, FreeBSD rc.d, and the `Null` backend).
+/// Linux/BSD: $XDG_DATA_HOME/shit/shitd.log (or platform data dir equivalent)
+/// Fallback: /tmp/shitd-$USER.log
+pub fn log_file_path() -> PathBuf {
+    if let Some(data_dir) = dirs::data_dir() {
+        let dir = data_dir.join("shit");
+        let _ = std::fs::create_dir_all(&dir);
+        return dir.join("shitd.log");
+    }
+    let user = std::env::var("USER").unwrap_or_else(|_| "unknown".into());
+    PathBuf::from(format!("/tmp/shitd-{}.log", user))
+}
+
+/// Log file size above which `RotatingLogFile` rotates the current file to
+/// `shitd.log.old` (clobbering any prior rotation) before continuing to
+/// write, so `shit daemon logs` has recent output without unbounded growth.
+const MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+
+/// `Write` sink handed to `env_logger`: the daemon's log output goes both to
+/// stderr (where systemd's journal picks it up for `journalctl`) and to
+/// `log_file_path()`, which the self-contained follower in the service
+/// module reads on backends with no native log viewer.
+struct RotatingLogFile {
+    path: PathBuf,
+    file: std::fs::File,
+    written: u64,
+}
+
+impl RotatingLogFile {
+    fn open(path: PathBuf) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        let written = file.metadata()?.len();
+        Ok(Self { path, file, written })
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let rotated = self.path.with_extension("log.old");
+        std::fs::rename(&self.path, &rotated)?;
+        self.file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl std::io::Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.written >= MAX_LOG_BYTES {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        let _ = std::io::stderr().write_all(&buf[..n]);
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Parses the `"<port> <token>"` contents written to the port file.
+pub fn read_port_file(path: &std::path::Path) -> Result<(u16, String)> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut parts = contents.trim().splitn(2, ' ');
+    let port: u16 = parts
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("empty port file"))?
+        .parse()?;
+    let token = parts.next().unwrap_or("").to_string();
+    Ok((port, token))
+}
+
 pub fn run_server() -> Result<()> {
+    let log_target = match RotatingLogFile::open(log_file_path()) {
+        Ok(file) => env_logger::Target::Pipe(Box::new(file)),
+        Err(_) => env_logger::Target::Stderr,
+    };
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
         .format_target(false)
+        .target(log_target)
         .init();
 
     const MAX_RETRIES: u32 = 10;
@@ -54,28 +156,54 @@ pub fn run_server() -> Result<()> {
         }
     };
     info!("loading model...");
-    let mut engine = Engine::new(&paths.model_path, &paths.tokenizer_path)?;
+    let engine = Engine::new(&paths.model_path, &paths.tokenizer_path)?;
+    let engine = Arc::new(Mutex::new(engine));
     info!("model loaded");
 
+    let token = generate_token();
+
     let server = tiny_http::Server::http("127.0.0.1:0")
         .map_err(|e| anyhow::anyhow!("failed to bind: {}", e))?;
     let port = server.server_addr().to_ip().unwrap().port();
+    let server = Arc::new(server);
 
     let port_file = port_file_path();
-    std::fs::write(&port_file, port.to_string())?;
-    info!("listening on 127.0.0.1:{}", port);
+    std::fs::write(&port_file, format!("{} {}", port, token))?;
+    restrict_to_owner(&port_file)?;
+    info!("listening on 127.0.0.1:{} (TCP, capability-token gated)", port);
 
-    // Clean up port file on shutdown
+    // Clean up the port file on shutdown
     let _guard = PortFileGuard(port_file);
 
+    // The Unix-domain-socket gateway is the preferred local transport: it's
+    // gated by filesystem permissions (0600) instead of a world-readable
+    // port number, so it needs no capability token.
+    #[cfg(unix)]
+    let _socket_guard = spawn_unix_listener(Arc::clone(&engine))?;
+
+    let state = DaemonState::new(Arc::clone(&engine), Arc::clone(&server));
+
     for request in server.incoming_requests() {
+        if !matches!(request.url(), "/health") && !has_valid_token(&request, &token) {
+            let resp = tiny_http::Response::from_string(r#"{"error":"missing or invalid token"}"#)
+                .with_status_code(401);
+            let _ = request.respond(resp);
+            continue;
+        }
+
         match (request.method(), request.url()) {
             (tiny_http::Method::Get, "/health") => {
                 let response = tiny_http::Response::from_string("ok");
                 let _ = request.respond(response);
             }
             (tiny_http::Method::Post, "/infer") => {
-                handle_infer(request, &mut engine);
+                handle_infer(request, &engine);
+            }
+            (tiny_http::Method::Post, "/infer/stream") => {
+                handle_infer_stream(request, &engine);
+            }
+            (tiny_http::Method::Post, "/rpc") => {
+                handle_rpc(request, &state);
             }
             _ => {
                 let response =
@@ -88,7 +216,200 @@ pub fn run_server() -> Result<()> {
     Ok(())
 }
 
-fn handle_infer(mut request: tiny_http::Request, engine: &mut Engine) {
+fn handle_rpc(mut request: tiny_http::Request, state: &DaemonState) {
+    let mut body = String::new();
+    if request.as_reader().read_to_string(&mut body).is_err() {
+        let resp =
+            tiny_http::Response::from_string(r#"{"error":"bad request"}"#).with_status_code(400);
+        let _ = request.respond(resp);
+        return;
+    }
+
+    let resp_body = crate::daemon::rpc::handle(&body, state);
+    let response = tiny_http::Response::from_string(resp_body)
+        .with_header("Content-Type: application/json".parse::<tiny_http::Header>().unwrap());
+    let _ = request.respond(response);
+}
+
+/// Resolves sampling params for one request: start from `config.toml`'s
+/// `[sampling]` defaults, then let any of the same field names present in
+/// the request body override them, so a caller can tweak just the knobs it
+/// cares about (e.g. `{"prompt": "...", "num_samples": 3}`).
+pub(crate) fn sampling_params_from_json(v: &serde_json::Value) -> SamplingParams {
+    let mut params = crate::config::load_config()
+        .map(|c| c.sampling_params())
+        .unwrap_or_default();
+
+    if let Some(temperature) = v["temperature"].as_f64() {
+        params.temperature = Some(temperature);
+    }
+    if let Some(top_k) = v["top_k"].as_u64() {
+        params.top_k = Some(top_k as usize);
+    }
+    if let Some(top_p) = v["top_p"].as_f64() {
+        params.top_p = Some(top_p);
+    }
+    if let Some(repeat_penalty) = v["repeat_penalty"].as_f64() {
+        params.repeat_penalty = repeat_penalty as f32;
+    }
+    if let Some(repeat_last_n) = v["repeat_last_n"].as_u64() {
+        params.repeat_last_n = repeat_last_n as usize;
+    }
+    if let Some(seed) = v["seed"].as_u64() {
+        params.seed = seed;
+    }
+    if let Some(max_tokens) = v["max_tokens"].as_u64() {
+        params.max_tokens = max_tokens as usize;
+    }
+    if let Some(num_samples) = v["num_samples"].as_u64() {
+        params.num_samples = num_samples as usize;
+    }
+
+    params
+}
+
+fn has_valid_token(request: &tiny_http::Request, expected: &str) -> bool {
+    request
+        .headers()
+        .iter()
+        .any(|h| h.field.as_str().as_str().eq_ignore_ascii_case(TOKEN_HEADER) && h.value == expected)
+}
+
+/// Derive a capability token from real OS entropy (`/dev/urandom`) rather
+/// than process-local state: this token is the sole auth on the TCP
+/// `/infer`/`/rpc` surface (including `shutdown`/`reload`), so it needs to
+/// be unguessable, not merely unpublished.
+#[cfg(unix)]
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    match std::fs::File::open("/dev/urandom").and_then(|mut f| f.read_exact(&mut bytes)) {
+        Ok(()) => hex_encode(&bytes),
+        // Extremely unlikely on any real Unix system; fall back to
+        // process-local entropy rather than failing the daemon outright.
+        Err(_) => generate_token_fallback(),
+    }
+}
+
+#[cfg(not(unix))]
+fn generate_token() -> String {
+    generate_token_fallback()
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Weaker fallback used only if `/dev/urandom` is unavailable, or on
+/// non-Unix platforms: a handful of process-local entropy sources (time,
+/// pid, and a stack address), hashed so none of them leak directly.
+fn generate_token_fallback() -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(std::process::id().to_le_bytes());
+    hasher.update(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+            .to_le_bytes(),
+    );
+    let stack_addr = &hasher as *const _ as usize;
+    hasher.update(stack_addr.to_le_bytes());
+
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(unix)]
+fn restrict_to_owner(path: &std::path::Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_to_owner(_path: &std::path::Path) -> Result<()> {
+    Ok(())
+}
+
+/// Bind the Unix-domain-socket gateway and serve `/infer`-equivalent
+/// requests on it using a small newline-delimited JSON protocol (rather
+/// than full HTTP, since the socket's filesystem permissions are already
+/// the trust boundary). Returns a guard that removes the socket on drop.
+#[cfg(unix)]
+fn spawn_unix_listener(engine: Arc<Mutex<Engine>>) -> Result<SocketFileGuard> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::UnixListener;
+
+    let path = socket_path();
+    let _ = std::fs::remove_file(&path); // clear a stale socket from a prior crash
+    let listener = UnixListener::bind(&path)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    info!("listening on {} (Unix socket)", path.display());
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let engine = Arc::clone(&engine);
+            std::thread::spawn(move || handle_unix_connection(stream, &engine));
+        }
+    });
+
+    Ok(SocketFileGuard(path))
+}
+
+/// One request per connection: a single JSON line in, then zero or more
+/// `{"token": "..."}` lines as the completion streams, followed by a final
+/// `{"fixes": [...]}` or `{"error": "..."}` line. Mirrors `/infer/stream`'s
+/// NDJSON shape so the socket transport doesn't have to give up streaming
+/// just because it skips the HTTP layer.
+#[cfg(unix)]
+fn handle_unix_connection(stream: std::os::unix::net::UnixStream, engine: &Mutex<Engine>) {
+    use std::io::{BufRead, Write};
+
+    let mut reader = std::io::BufReader::new(stream.try_clone().expect("clone unix stream"));
+    let mut writer = stream;
+
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+        return;
+    }
+
+    let (prompt, params) = match serde_json::from_str::<serde_json::Value>(&line) {
+        Ok(v) => (
+            v["prompt"].as_str().unwrap_or("").to_string(),
+            sampling_params_from_json(&v),
+        ),
+        Err(_) => {
+            let _ = writeln!(writer, r#"{{"error":"invalid json"}}"#);
+            return;
+        }
+    };
+
+    let result = {
+        let mut engine = match engine.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut on_fragment = |fragment: &str| -> Result<()> {
+            let line = serde_json::json!({"token": fragment}).to_string();
+            writeln!(writer, "{line}")?;
+            Ok(())
+        };
+        crate::model::infer_with_engine_streaming(&mut engine, &prompt, &params, &mut on_fragment)
+    };
+
+    let response = match result {
+        Ok(fixes) => serde_json::json!({"fixes": fixes}).to_string(),
+        Err(e) => {
+            error!("inference failed: {}", e);
+            serde_json::json!({"error": e.to_string()}).to_string()
+        }
+    };
+    let _ = writeln!(writer, "{}", response);
+}
+
+fn handle_infer(mut request: tiny_http::Request, engine: &Mutex<Engine>) {
     let mut body = String::new();
     if request.as_reader().read_to_string(&mut body).is_err() {
         let resp =
@@ -98,8 +419,11 @@ fn handle_infer(mut request: tiny_http::Request, engine: &mut Engine) {
     }
 
     let parsed: Result<serde_json::Value, _> = serde_json::from_str(&body);
-    let prompt = match parsed {
-        Ok(v) => v["prompt"].as_str().unwrap_or("").to_string(),
+    let (prompt, params) = match parsed {
+        Ok(v) => (
+            v["prompt"].as_str().unwrap_or("").to_string(),
+            sampling_params_from_json(&v),
+        ),
         Err(_) => {
             let resp = tiny_http::Response::from_string(r#"{"error":"invalid json"}"#)
                 .with_status_code(400);
@@ -108,7 +432,13 @@ fn handle_infer(mut request: tiny_http::Request, engine: &mut Engine) {
         }
     };
 
-    let result = crate::model::infer_with_engine(engine, &prompt);
+    let result = {
+        let mut engine = match engine.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        crate::model::infer_with_engine(&mut engine, &prompt, &params)
+    };
     let fixes = match result {
         Ok(fixes) => fixes,
         Err(e) => {
@@ -128,6 +458,113 @@ fn handle_infer(mut request: tiny_http::Request, engine: &mut Engine) {
     let _ = request.respond(response);
 }
 
+/// Stream one NDJSON line (`{"token": "..."}`) per decoded fragment, followed
+/// by a final `{"fixes": [...]}` or `{"error": "..."}` line, flushing each
+/// line to the client as soon as it's produced instead of buffering the
+/// whole response.
+fn handle_infer_stream(mut request: tiny_http::Request, engine: &Mutex<Engine>) {
+    let mut body = String::new();
+    if request.as_reader().read_to_string(&mut body).is_err() {
+        let resp =
+            tiny_http::Response::from_string(r#"{"error":"bad request"}"#).with_status_code(400);
+        let _ = request.respond(resp);
+        return;
+    }
+
+    let parsed: Result<serde_json::Value, _> = serde_json::from_str(&body);
+    let (prompt, params) = match parsed {
+        Ok(v) => (
+            v["prompt"].as_str().unwrap_or("").to_string(),
+            sampling_params_from_json(&v),
+        ),
+        Err(_) => {
+            let resp = tiny_http::Response::from_string(r#"{"error":"invalid json"}"#)
+                .with_status_code(400);
+            let _ = request.respond(resp);
+            return;
+        }
+    };
+
+    let (tx, rx) = mpsc::channel::<Vec<u8>>();
+    let reader = ChunkReader {
+        rx,
+        pending: VecDeque::new(),
+    };
+    let response = tiny_http::Response::new(
+        tiny_http::StatusCode(200),
+        vec![
+            tiny_http::Header::from_bytes(&b"Transfer-Encoding"[..], &b"chunked"[..]).unwrap(),
+            tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/x-ndjson"[..])
+                .unwrap(),
+        ],
+        reader,
+        None,
+        None,
+    );
+
+    // `thread::scope` lets the worker borrow `engine` for the duration of
+    // this call; the closure is `move` so it owns `tx` outright and drops it
+    // when it returns, since tiny_http pulls from `reader` (and thus blocks
+    // on `rx`) until every `tx` is gone and the response completes.
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut on_token = |fragment: &str| -> Result<()> {
+                let line = serde_json::json!({"token": fragment}).to_string();
+                let _ = tx.send(format!("{line}\n").into_bytes());
+                Ok(())
+            };
+
+            let result = {
+                let mut engine = match engine.lock() {
+                    Ok(guard) => guard,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                crate::model::infer_with_engine_streaming(
+                    &mut engine,
+                    &prompt,
+                    &params,
+                    &mut on_token,
+                )
+            };
+            let final_line = match result {
+                Ok(fixes) => serde_json::json!({"fixes": fixes}).to_string(),
+                Err(e) => {
+                    error!("streaming inference failed: {}", e);
+                    serde_json::json!({"error": e.to_string()}).to_string()
+                }
+            };
+            let _ = tx.send(format!("{final_line}\n").into_bytes());
+        });
+
+        let _ = request.respond(response);
+    });
+}
+
+/// `Read` adapter that pulls NDJSON chunks off an `mpsc::Receiver` as
+/// `tiny_http` drains the response, so bytes reach the client as soon as
+/// they're produced rather than after generation finishes.
+struct ChunkReader {
+    rx: mpsc::Receiver<Vec<u8>>,
+    pending: VecDeque<u8>,
+}
+
+impl std::io::Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() {
+            match self.rx.recv() {
+                Ok(chunk) => self.pending.extend(chunk),
+                Err(_) => return Ok(0), // sender dropped: end of stream
+            }
+        }
+
+        let n = buf.len().min(self.pending.len());
+        for slot in buf.iter_mut().take(n) {
+            *slot = self.pending.pop_front().unwrap();
+        }
+        Ok(n)
+    }
+}
+
 /// RAII guard that removes the port file when the server shuts down.
 struct PortFileGuard(PathBuf);
 
@@ -136,3 +573,15 @@ impl Drop for PortFileGuard {
         let _ = std::fs::remove_file(&self.0);
     }
 }
+
+/// RAII guard that removes the Unix-domain-socket file when the server
+/// shuts down.
+#[cfg(unix)]
+struct SocketFileGuard(PathBuf);
+
+#[cfg(unix)]
+impl Drop for SocketFileGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
@@ -1,81 +1,98 @@
+use crate::config::ServiceConfig;
 use anyhow::{bail, Result};
+use std::io::{Read as _, Seek, SeekFrom};
 use std::path::PathBuf;
-
-enum ServiceManager {
-    Systemd,
-    Launchd,
+use std::time::Duration;
+
+/// A platform's way of running `shitd` as a background service: installing
+/// it to start on boot/login, starting/stopping/restarting it, and reading
+/// its logs. Each supported init system gets its own implementation;
+/// `detect_service_manager` picks one by probing the platform for the
+/// relevant marker (`/run/systemd/system`, `/sbin/openrc`, etc). The
+/// `[service]` section of `config.toml` (see `crate::config::ServiceConfig`)
+/// is threaded through so backends can honor overrides instead of the
+/// hardcoded defaults below.
+trait ServiceManager {
+    fn is_installed(&self, cfg: &ServiceConfig) -> Result<bool>;
+    fn install(&self, cfg: &ServiceConfig) -> Result<()>;
+    fn uninstall(&self, cfg: &ServiceConfig) -> Result<()>;
+    fn start(&self, cfg: &ServiceConfig) -> Result<()>;
+    fn stop(&self, cfg: &ServiceConfig) -> Result<()>;
+    fn restart(&self, cfg: &ServiceConfig) -> Result<()>;
+    fn status(&self, cfg: &ServiceConfig) -> Result<()>;
+    fn logs(&self, cfg: &ServiceConfig, follow: bool) -> Result<()>;
 }
 
-fn detect_service_manager() -> Result<ServiceManager> {
+fn detect_service_manager() -> Box<dyn ServiceManager> {
     if cfg!(target_os = "macos") {
-        return Ok(ServiceManager::Launchd);
+        return Box::new(Launchd);
     }
     if PathBuf::from("/run/systemd/system").exists() {
-        return Ok(ServiceManager::Systemd);
+        return Box::new(Systemd);
+    }
+    if PathBuf::from("/sbin/openrc").exists() || PathBuf::from("/run/openrc").exists() {
+        return Box::new(OpenRc);
+    }
+    if cfg!(target_os = "freebsd") {
+        return Box::new(FreeBsdRc);
     }
-    bail!("unsupported platform: neither systemd nor launchd detected")
+    Box::new(Null)
 }
 
 fn binary_path() -> Result<PathBuf> {
     Ok(std::env::current_exe()?)
 }
 
+fn service_config() -> Result<ServiceConfig> {
+    Ok(crate::config::load_config()?.service.unwrap_or_default())
+}
+
+/// Substitutes the `{unit}` placeholder in a custom argument template with
+/// the configured (or default) unit name.
+fn expand_args(args: &[String], unit: &str) -> Vec<String> {
+    args.iter().map(|arg| arg.replace("{unit}", unit)).collect()
+}
+
 // --- public API ---
 
 pub fn is_installed() -> Result<bool> {
-    match detect_service_manager()? {
-        ServiceManager::Systemd => Ok(systemd_unit_path()?.exists()),
-        ServiceManager::Launchd => Ok(launchd_plist_path()?.exists()),
-    }
+    detect_service_manager().is_installed(&service_config()?)
 }
 
 pub fn install() -> Result<()> {
-    match detect_service_manager()? {
-        ServiceManager::Systemd => install_systemd(),
-        ServiceManager::Launchd => install_launchd(),
-    }
+    detect_service_manager().install(&service_config()?)
 }
 
 pub fn uninstall() -> Result<()> {
-    match detect_service_manager()? {
-        ServiceManager::Systemd => uninstall_systemd(),
-        ServiceManager::Launchd => uninstall_launchd(),
-    }
+    detect_service_manager().uninstall(&service_config()?)
 }
 
 pub fn start() -> Result<()> {
-    match detect_service_manager()? {
-        ServiceManager::Systemd => start_systemd(),
-        ServiceManager::Launchd => start_launchd(),
-    }
+    detect_service_manager().start(&service_config()?)
 }
 
 pub fn stop() -> Result<()> {
-    match detect_service_manager()? {
-        ServiceManager::Systemd => stop_systemd(),
-        ServiceManager::Launchd => stop_launchd(),
-    }
+    detect_service_manager().stop(&service_config()?)
 }
 
 pub fn restart() -> Result<()> {
-    match detect_service_manager()? {
-        ServiceManager::Systemd => restart_systemd(),
-        ServiceManager::Launchd => {
-            let _ = stop_launchd();
-            start_launchd()
-        }
-    }
+    detect_service_manager().restart(&service_config()?)
+}
+
+pub fn status() -> Result<()> {
+    detect_service_manager().status(&service_config()?)
 }
 
 pub fn logs(follow: bool) -> Result<()> {
-    match detect_service_manager()? {
-        ServiceManager::Systemd => logs_systemd(follow),
-        ServiceManager::Launchd => logs_launchd(follow),
-    }
+    detect_service_manager().logs(&service_config()?, follow)
 }
 
 // --- systemd ---
 
+struct Systemd;
+
+const DEFAULT_SYSTEMD_UNIT: &str = "shitd";
+
 fn systemd_unit_path() -> Result<PathBuf> {
     let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("cannot determine home directory"))?;
     let dir = home.join(".config/systemd/user");
@@ -83,114 +100,185 @@ fn systemd_unit_path() -> Result<PathBuf> {
     Ok(dir.join("shitd.service"))
 }
 
-fn install_systemd() -> Result<()> {
-    let bin = binary_path()?;
-    let unit_path = systemd_unit_path()?;
-
-    let unit = format!(
-        "[Unit]\n\
-         Description=shit daemon — keeps model in memory for fast inference\n\
-         \n\
-         [Service]\n\
-         ExecStart={} daemon run\n\
-         Restart=on-failure\n\
-         \n\
-         [Install]\n\
-         WantedBy=default.target\n",
-        bin.display()
-    );
-
-    std::fs::write(&unit_path, unit)?;
-    eprintln!("shitd: wrote {}", unit_path.display());
-
-    let status = std::process::Command::new("systemctl")
-        .args(["--user", "daemon-reload"])
-        .status()?;
-    if !status.success() {
-        bail!("systemctl daemon-reload failed");
-    }
-
-    let status = std::process::Command::new("systemctl")
-        .args(["--user", "enable", "shitd"])
-        .status()?;
-    if !status.success() {
-        bail!("systemctl enable shitd failed");
-    }
-
-    eprintln!("shitd: service installed and enabled");
-    Ok(())
-}
+impl Systemd {
+    fn manager_bin(cfg: &ServiceConfig) -> &str {
+        cfg.manager_bin.as_deref().unwrap_or("systemctl")
+    }
+
+    fn unit(cfg: &ServiceConfig) -> &str {
+        cfg.unit_name.as_deref().unwrap_or(DEFAULT_SYSTEMD_UNIT)
+    }
 
-fn uninstall_systemd() -> Result<()> {
-    let unit_path = systemd_unit_path()?;
+    fn scope_args(cfg: &ServiceConfig) -> Vec<String> {
+        if cfg.system_scope.unwrap_or(false) {
+            vec![]
+        } else {
+            vec!["--user".to_string()]
+        }
+    }
+
+    fn run(&self, cfg: &ServiceConfig, args: Vec<String>, failure: &str) -> Result<()> {
+        let status = std::process::Command::new(Self::manager_bin(cfg))
+            .args(&args)
+            .status()?;
+        if !status.success() {
+            bail!("{failure} failed");
+        }
+        Ok(())
+    }
+}
 
-    let _ = std::process::Command::new("systemctl")
-        .args(["--user", "disable", "shitd"])
-        .status();
+impl ServiceManager for Systemd {
+    fn is_installed(&self, _cfg: &ServiceConfig) -> Result<bool> {
+        Ok(systemd_unit_path()?.exists())
+    }
 
-    if unit_path.exists() {
-        std::fs::remove_file(&unit_path)?;
-        eprintln!("shitd: removed {}", unit_path.display());
+    fn install(&self, cfg: &ServiceConfig) -> Result<()> {
+        let bin = binary_path()?;
+        let unit_path = systemd_unit_path()?;
+
+        let unit = format!(
+            "[Unit]\n\
+             Description=shit daemon — keeps model in memory for fast inference\n\
+             \n\
+             [Service]\n\
+             ExecStart={} daemon run\n\
+             Restart=on-failure\n\
+             \n\
+             [Install]\n\
+             WantedBy=default.target\n",
+            bin.display()
+        );
+
+        std::fs::write(&unit_path, unit)?;
+        eprintln!("shitd: wrote {}", unit_path.display());
+
+        let mut reload_args = Self::scope_args(cfg);
+        reload_args.push("daemon-reload".to_string());
+        self.run(cfg, reload_args, "systemctl daemon-reload")?;
+
+        let enable_args = match &cfg.enable_args {
+            Some(args) => expand_args(args, Self::unit(cfg)),
+            None => {
+                let mut args = Self::scope_args(cfg);
+                args.extend(["enable".to_string(), Self::unit(cfg).to_string()]);
+                args
+            }
+        };
+        self.run(cfg, enable_args, "systemctl enable")?;
+
+        eprintln!("shitd: service installed and enabled");
+        Ok(())
     }
 
-    let _ = std::process::Command::new("systemctl")
-        .args(["--user", "daemon-reload"])
-        .status();
+    fn uninstall(&self, cfg: &ServiceConfig) -> Result<()> {
+        let unit_path = systemd_unit_path()?;
 
-    eprintln!("shitd: service uninstalled");
-    Ok(())
-}
+        let mut disable_args = Self::scope_args(cfg);
+        disable_args.extend(["disable".to_string(), Self::unit(cfg).to_string()]);
+        let _ = std::process::Command::new(Self::manager_bin(cfg))
+            .args(&disable_args)
+            .status();
 
-fn start_systemd() -> Result<()> {
-    let status = std::process::Command::new("systemctl")
-        .args(["--user", "start", "shitd"])
-        .status()?;
-    if !status.success() {
-        bail!("systemctl start shitd failed");
+        if unit_path.exists() {
+            std::fs::remove_file(&unit_path)?;
+            eprintln!("shitd: removed {}", unit_path.display());
+        }
+
+        let mut reload_args = Self::scope_args(cfg);
+        reload_args.push("daemon-reload".to_string());
+        let _ = std::process::Command::new(Self::manager_bin(cfg))
+            .args(&reload_args)
+            .status();
+
+        eprintln!("shitd: service uninstalled");
+        Ok(())
     }
-    eprintln!("shitd: started");
-    Ok(())
-}
 
-fn stop_systemd() -> Result<()> {
-    let status = std::process::Command::new("systemctl")
-        .args(["--user", "stop", "shitd"])
-        .status()?;
-    if !status.success() {
-        bail!("systemctl stop shitd failed");
+    fn start(&self, cfg: &ServiceConfig) -> Result<()> {
+        let args = match &cfg.start_args {
+            Some(args) => expand_args(args, Self::unit(cfg)),
+            None => {
+                let mut args = Self::scope_args(cfg);
+                args.extend(["start".to_string(), Self::unit(cfg).to_string()]);
+                args
+            }
+        };
+        self.run(cfg, args, "systemctl start")?;
+        eprintln!("shitd: started");
+        Ok(())
     }
-    eprintln!("shitd: stopped");
-    Ok(())
-}
 
-fn restart_systemd() -> Result<()> {
-    let status = std::process::Command::new("systemctl")
-        .args(["--user", "restart", "shitd"])
-        .status()?;
-    if !status.success() {
-        bail!("systemctl restart shitd failed");
+    fn stop(&self, cfg: &ServiceConfig) -> Result<()> {
+        let args = match &cfg.stop_args {
+            Some(args) => expand_args(args, Self::unit(cfg)),
+            None => {
+                let mut args = Self::scope_args(cfg);
+                args.extend(["stop".to_string(), Self::unit(cfg).to_string()]);
+                args
+            }
+        };
+        self.run(cfg, args, "systemctl stop")?;
+        eprintln!("shitd: stopped");
+        Ok(())
     }
-    eprintln!("shitd: restarted");
-    Ok(())
-}
 
-fn logs_systemd(follow: bool) -> Result<()> {
-    let mut args = vec!["--user", "-u", "shitd", "-n", "50", "--no-pager"];
-    if follow {
-        args.push("-f");
+    fn restart(&self, cfg: &ServiceConfig) -> Result<()> {
+        let mut args = Self::scope_args(cfg);
+        args.extend(["restart".to_string(), Self::unit(cfg).to_string()]);
+        self.run(cfg, args, "systemctl restart")?;
+        eprintln!("shitd: restarted");
+        Ok(())
     }
-    let status = std::process::Command::new("journalctl")
-        .args(&args)
-        .status()?;
-    if !status.success() {
-        bail!("journalctl failed");
+
+    fn status(&self, cfg: &ServiceConfig) -> Result<()> {
+        let args = match &cfg.status_args {
+            Some(args) => expand_args(args, Self::unit(cfg)),
+            None => {
+                let mut args = Self::scope_args(cfg);
+                args.extend(["status".to_string(), Self::unit(cfg).to_string()]);
+                args
+            }
+        };
+        // `systemctl status` exits non-zero for a stopped-but-installed
+        // unit, which isn't a failure worth bailing on here — just forward
+        // whatever it printed.
+        std::process::Command::new(Self::manager_bin(cfg))
+            .args(&args)
+            .status()?;
+        Ok(())
+    }
+
+    fn logs(&self, cfg: &ServiceConfig, follow: bool) -> Result<()> {
+        let args = match &cfg.logs_args {
+            Some(args) => expand_args(args, Self::unit(cfg)),
+            None => {
+                let mut args = Self::scope_args(cfg);
+                args.extend(
+                    ["-u", Self::unit(cfg), "-n", "50", "--no-pager"]
+                        .map(str::to_string),
+                );
+                args
+            }
+        };
+        let mut command = std::process::Command::new("journalctl");
+        command.args(&args);
+        if follow {
+            command.arg("-f");
+        }
+        let status = command.status()?;
+        if !status.success() {
+            bail!("journalctl failed");
+        }
+        Ok(())
     }
-    Ok(())
 }
 
 // --- launchd ---
 
-const LAUNCHD_LABEL: &str = "dev.ava.shitd";
+struct Launchd;
+
+const DEFAULT_LAUNCHD_LABEL: &str = "dev.ava.shitd";
 
 fn launchd_plist_path() -> Result<PathBuf> {
     let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("cannot determine home directory"))?;
@@ -199,12 +287,23 @@ fn launchd_plist_path() -> Result<PathBuf> {
     Ok(dir.join("dev.ava.shitd.plist"))
 }
 
-fn install_launchd() -> Result<()> {
-    let bin = binary_path()?;
-    let plist_path = launchd_plist_path()?;
+impl Launchd {
+    fn label(cfg: &ServiceConfig) -> &str {
+        cfg.unit_name.as_deref().unwrap_or(DEFAULT_LAUNCHD_LABEL)
+    }
+}
+
+impl ServiceManager for Launchd {
+    fn is_installed(&self, _cfg: &ServiceConfig) -> Result<bool> {
+        Ok(launchd_plist_path()?.exists())
+    }
+
+    fn install(&self, cfg: &ServiceConfig) -> Result<()> {
+        let bin = binary_path()?;
+        let plist_path = launchd_plist_path()?;
 
-    let plist = format!(
-        r#"<?xml version="1.0" encoding="UTF-8"?>
+        let plist = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
 <plist version="1.0">
 <dict>
@@ -223,65 +322,418 @@ fn install_launchd() -> Result<()> {
 </dict>
 </plist>
 "#,
-        label = LAUNCHD_LABEL,
-        bin = bin.display()
-    );
-
-    std::fs::write(&plist_path, plist)?;
-    eprintln!("shitd: wrote {}", plist_path.display());
-    eprintln!("shitd: service installed");
-    Ok(())
+            label = Self::label(cfg),
+            bin = bin.display()
+        );
+
+        std::fs::write(&plist_path, plist)?;
+        eprintln!("shitd: wrote {}", plist_path.display());
+        eprintln!("shitd: service installed");
+        Ok(())
+    }
+
+    fn uninstall(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let plist_path = launchd_plist_path()?;
+
+        if plist_path.exists() {
+            std::fs::remove_file(&plist_path)?;
+            eprintln!("shitd: removed {}", plist_path.display());
+        }
+
+        eprintln!("shitd: service uninstalled");
+        Ok(())
+    }
+
+    fn start(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let plist_path = launchd_plist_path()?;
+        let status = std::process::Command::new("launchctl")
+            .args(["load", &plist_path.to_string_lossy()])
+            .status()?;
+        if !status.success() {
+            bail!("launchctl load failed");
+        }
+        eprintln!("shitd: started");
+        Ok(())
+    }
+
+    fn stop(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let plist_path = launchd_plist_path()?;
+        let status = std::process::Command::new("launchctl")
+            .args(["unload", &plist_path.to_string_lossy()])
+            .status()?;
+        if !status.success() {
+            bail!("launchctl unload failed");
+        }
+        eprintln!("shitd: stopped");
+        Ok(())
+    }
+
+    fn restart(&self, cfg: &ServiceConfig) -> Result<()> {
+        let _ = self.stop(cfg);
+        self.start(cfg)
+    }
+
+    fn status(&self, cfg: &ServiceConfig) -> Result<()> {
+        let args = match &cfg.status_args {
+            Some(args) => expand_args(args, Self::label(cfg)),
+            None => vec!["list".to_string(), Self::label(cfg).to_string()],
+        };
+        // As with systemctl, `launchctl list` exits non-zero when the label
+        // isn't loaded — not worth bailing on, just forward the output.
+        std::process::Command::new("launchctl").args(&args).status()?;
+        Ok(())
+    }
+
+    fn logs(&self, _cfg: &ServiceConfig, follow: bool) -> Result<()> {
+        let predicate = format!("process == \"{}\"", "shit");
+        let status = if follow {
+            std::process::Command::new("log")
+                .args(["stream", "--predicate", &predicate, "--style", "compact"])
+                .status()?
+        } else {
+            std::process::Command::new("log")
+                .args([
+                    "show",
+                    "--predicate",
+                    &predicate,
+                    "--style",
+                    "compact",
+                    "--last",
+                    "5m",
+                ])
+                .status()?
+        };
+        if !status.success() {
+            bail!("log {} failed", if follow { "stream" } else { "show" });
+        }
+        Ok(())
+    }
 }
 
-fn uninstall_launchd() -> Result<()> {
-    let plist_path = launchd_plist_path()?;
+// --- OpenRC (Alpine/Gentoo) ---
+
+struct OpenRc;
+
+const OPENRC_SCRIPT_PATH: &str = "/etc/init.d/shitd";
+
+impl ServiceManager for OpenRc {
+    fn is_installed(&self, _cfg: &ServiceConfig) -> Result<bool> {
+        Ok(PathBuf::from(OPENRC_SCRIPT_PATH).exists())
+    }
+
+    fn install(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let bin = binary_path()?;
+
+        let script = format!(
+            "#!/sbin/openrc-run\n\
+             \n\
+             name=\"shitd\"\n\
+             description=\"shit daemon — keeps model in memory for fast inference\"\n\
+             command=\"{}\"\n\
+             command_args=\"daemon run\"\n\
+             command_background=\"yes\"\n\
+             pidfile=\"/run/${{RC_SVCNAME}}.pid\"\n",
+            bin.display()
+        );
+
+        std::fs::write(OPENRC_SCRIPT_PATH, script)?;
+        std::process::Command::new("chmod")
+            .args(["755", OPENRC_SCRIPT_PATH])
+            .status()?;
+        eprintln!("shitd: wrote {}", OPENRC_SCRIPT_PATH);
+
+        let status = std::process::Command::new("rc-update")
+            .args(["add", "shitd", "default"])
+            .status()?;
+        if !status.success() {
+            bail!("rc-update add shitd failed");
+        }
+
+        eprintln!("shitd: service installed and enabled");
+        Ok(())
+    }
+
+    fn uninstall(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let _ = std::process::Command::new("rc-update")
+            .args(["del", "shitd", "default"])
+            .status();
+
+        let path = PathBuf::from(OPENRC_SCRIPT_PATH);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+            eprintln!("shitd: removed {}", OPENRC_SCRIPT_PATH);
+        }
+
+        eprintln!("shitd: service uninstalled");
+        Ok(())
+    }
+
+    fn start(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let status = std::process::Command::new("rc-service")
+            .args(["shitd", "start"])
+            .status()?;
+        if !status.success() {
+            bail!("rc-service shitd start failed");
+        }
+        eprintln!("shitd: started");
+        Ok(())
+    }
+
+    fn stop(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let status = std::process::Command::new("rc-service")
+            .args(["shitd", "stop"])
+            .status()?;
+        if !status.success() {
+            bail!("rc-service shitd stop failed");
+        }
+        eprintln!("shitd: stopped");
+        Ok(())
+    }
+
+    fn restart(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let status = std::process::Command::new("rc-service")
+            .args(["shitd", "restart"])
+            .status()?;
+        if !status.success() {
+            bail!("rc-service shitd restart failed");
+        }
+        eprintln!("shitd: restarted");
+        Ok(())
+    }
 
-    if plist_path.exists() {
-        std::fs::remove_file(&plist_path)?;
-        eprintln!("shitd: removed {}", plist_path.display());
+    fn status(&self, cfg: &ServiceConfig) -> Result<()> {
+        let args = match &cfg.status_args {
+            Some(args) => expand_args(args, "shitd"),
+            None => vec!["shitd".to_string(), "status".to_string()],
+        };
+        std::process::Command::new("rc-service").args(&args).status()?;
+        Ok(())
     }
 
-    eprintln!("shitd: service uninstalled");
-    Ok(())
+    fn logs(&self, _cfg: &ServiceConfig, follow: bool) -> Result<()> {
+        follow_log_file(follow)
+    }
 }
 
-fn start_launchd() -> Result<()> {
-    let plist_path = launchd_plist_path()?;
-    let status = std::process::Command::new("launchctl")
-        .args(["load", &plist_path.to_string_lossy()])
-        .status()?;
-    if !status.success() {
-        bail!("launchctl load failed");
+// --- FreeBSD rc.d ---
+
+struct FreeBsdRc;
+
+const FREEBSD_SCRIPT_PATH: &str = "/usr/local/etc/rc.d/shitd";
+
+impl ServiceManager for FreeBsdRc {
+    fn is_installed(&self, _cfg: &ServiceConfig) -> Result<bool> {
+        Ok(PathBuf::from(FREEBSD_SCRIPT_PATH).exists())
+    }
+
+    fn install(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let bin = binary_path()?;
+
+        let script = format!(
+            "#!/bin/sh\n\
+             #\n\
+             # PROVIDE: shitd\n\
+             # REQUIRE: NETWORKING\n\
+             # KEYWORD: shutdown\n\
+             \n\
+             . /etc/rc.subr\n\
+             \n\
+             name=\"shitd\"\n\
+             rcvar=\"shitd_enable\"\n\
+             command=\"{}\"\n\
+             command_args=\"daemon run &\"\n\
+             pidfile=\"/var/run/${{name}}.pid\"\n\
+             \n\
+             load_rc_config $name\n\
+             run_rc_command \"$1\"\n",
+            bin.display()
+        );
+
+        std::fs::write(FREEBSD_SCRIPT_PATH, script)?;
+        std::process::Command::new("chmod")
+            .args(["755", FREEBSD_SCRIPT_PATH])
+            .status()?;
+        eprintln!("shitd: wrote {}", FREEBSD_SCRIPT_PATH);
+
+        let status = std::process::Command::new("sysrc")
+            .args(["shitd_enable=YES"])
+            .status()?;
+        if !status.success() {
+            bail!("sysrc shitd_enable=YES failed");
+        }
+
+        eprintln!("shitd: service installed and enabled");
+        Ok(())
+    }
+
+    fn uninstall(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let _ = std::process::Command::new("sysrc")
+            .args(["-x", "shitd_enable"])
+            .status();
+
+        let path = PathBuf::from(FREEBSD_SCRIPT_PATH);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+            eprintln!("shitd: removed {}", FREEBSD_SCRIPT_PATH);
+        }
+
+        eprintln!("shitd: service uninstalled");
+        Ok(())
+    }
+
+    fn start(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let status = std::process::Command::new("service")
+            .args(["shitd", "start"])
+            .status()?;
+        if !status.success() {
+            bail!("service shitd start failed");
+        }
+        eprintln!("shitd: started");
+        Ok(())
+    }
+
+    fn stop(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let status = std::process::Command::new("service")
+            .args(["shitd", "stop"])
+            .status()?;
+        if !status.success() {
+            bail!("service shitd stop failed");
+        }
+        eprintln!("shitd: stopped");
+        Ok(())
+    }
+
+    fn restart(&self, _cfg: &ServiceConfig) -> Result<()> {
+        let status = std::process::Command::new("service")
+            .args(["shitd", "restart"])
+            .status()?;
+        if !status.success() {
+            bail!("service shitd restart failed");
+        }
+        eprintln!("shitd: restarted");
+        Ok(())
+    }
+
+    fn status(&self, cfg: &ServiceConfig) -> Result<()> {
+        let args = match &cfg.status_args {
+            Some(args) => expand_args(args, "shitd"),
+            None => vec!["shitd".to_string(), "status".to_string()],
+        };
+        std::process::Command::new("service").args(&args).status()?;
+        Ok(())
+    }
+
+    fn logs(&self, _cfg: &ServiceConfig, follow: bool) -> Result<()> {
+        follow_log_file(follow)
     }
-    eprintln!("shitd: started");
-    Ok(())
 }
 
-fn stop_launchd() -> Result<()> {
-    let plist_path = launchd_plist_path()?;
-    let status = std::process::Command::new("launchctl")
-        .args(["unload", &plist_path.to_string_lossy()])
-        .status()?;
-    if !status.success() {
-        bail!("launchctl unload failed");
+// --- no-op fallback ---
+
+/// Returned when no known service manager is detected. Every management
+/// operation prints a clear, non-fatal message rather than a `bail!`, and
+/// `logs` reads the daemon's own log file, so `shit daemon run` in the
+/// foreground still works even without OS-level service support.
+struct Null;
+
+impl ServiceManager for Null {
+    fn is_installed(&self, _cfg: &ServiceConfig) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn install(&self, _cfg: &ServiceConfig) -> Result<()> {
+        eprintln!(
+            "shitd: service management unavailable on this platform; run `shit daemon run` manually instead"
+        );
+        Ok(())
+    }
+
+    fn uninstall(&self, _cfg: &ServiceConfig) -> Result<()> {
+        eprintln!("shitd: service management unavailable on this platform; nothing to uninstall");
+        Ok(())
+    }
+
+    fn start(&self, _cfg: &ServiceConfig) -> Result<()> {
+        eprintln!(
+            "shitd: service management unavailable on this platform; run `shit daemon run` manually instead"
+        );
+        Ok(())
+    }
+
+    fn stop(&self, _cfg: &ServiceConfig) -> Result<()> {
+        eprintln!(
+            "shitd: service management unavailable on this platform; stop the `shit daemon run` process manually"
+        );
+        Ok(())
+    }
+
+    fn restart(&self, _cfg: &ServiceConfig) -> Result<()> {
+        eprintln!(
+            "shitd: service management unavailable on this platform; restart the `shit daemon run` process manually"
+        );
+        Ok(())
+    }
+
+    fn status(&self, _cfg: &ServiceConfig) -> Result<()> {
+        eprintln!("shitd: service management unavailable on this platform; check the `shit daemon run` process manually");
+        Ok(())
+    }
+
+    fn logs(&self, _cfg: &ServiceConfig, follow: bool) -> Result<()> {
+        follow_log_file(follow)
     }
-    eprintln!("shitd: stopped");
-    Ok(())
 }
 
-fn logs_launchd(follow: bool) -> Result<()> {
-    let predicate = format!("process == \"{}\"", "shit");
-    let status = if follow {
-        std::process::Command::new("log")
-            .args(["stream", "--predicate", &predicate, "--style", "compact"])
-            .status()?
-    } else {
-        std::process::Command::new("log")
-            .args(["show", "--predicate", &predicate, "--style", "compact", "--last", "5m"])
-            .status()?
-    };
-    if !status.success() {
-        bail!("log {} failed", if follow { "stream" } else { "show" });
-    }
-    Ok(())
+/// Number of trailing lines printed before following (or, with `follow:
+/// false`, the entire non-follow output).
+const TAIL_LINES: usize = 50;
+
+/// How often to re-check the log file's length while following.
+const TAIL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Self-contained `tail -f` over the daemon's own log file
+/// (`server::log_file_path()`), used by backends (OpenRC, FreeBSD rc.d, and
+/// the `Null` fallback) with no platform-native log viewer. Avoids an
+/// inotify/kqueue dependency by polling the file's length: growth is read
+/// and printed incrementally, and a shrink (rotation or truncation) reopens
+/// the file from the start.
+fn follow_log_file(follow: bool) -> Result<()> {
+    let path = crate::daemon::server::log_file_path();
+    if !path.exists() {
+        eprintln!("shitd: no log file yet at {}", path.display());
+        return Ok(());
+    }
+
+    let contents = std::fs::read_to_string(&path).unwrap_or_default();
+    let tail: Vec<&str> = contents.lines().rev().take(TAIL_LINES).collect();
+    for line in tail.into_iter().rev() {
+        println!("{line}");
+    }
+
+    if !follow {
+        return Ok(());
+    }
+
+    let mut file = std::fs::File::open(&path)?;
+    let mut offset = file.metadata()?.len();
+    file.seek(SeekFrom::Start(offset))?;
+
+    loop {
+        std::thread::sleep(TAIL_POLL_INTERVAL);
+
+        let current_len = std::fs::metadata(&path)?.len();
+        if current_len < offset {
+            // Log file was rotated or truncated; start over from the top.
+            file = std::fs::File::open(&path)?;
+            offset = 0;
+        }
+        if current_len > offset {
+            let mut buf = String::new();
+            file.read_to_string(&mut buf)?;
+            print!("{buf}");
+            use std::io::Write as _;
+            let _ = std::io::stdout().flush();
+            offset = current_len;
+        }
+    }
 }
@@ -3,6 +3,7 @@ mod config;
 mod daemon;
 mod model;
 mod prompt;
+mod selector;
 mod shell;
 
 use anyhow::Result;
@@ -41,14 +42,26 @@ enum Command {
 #[cfg(feature = "daemon")]
 #[derive(Subcommand)]
 pub enum DaemonCommand {
-    /// Start the daemon server (foreground)
+    /// Run the daemon server directly (foreground, no service manager)
+    Run,
+    /// Start the daemon as a service, installing it first if needed
     Start,
+    /// Stop the daemon service
+    Stop,
+    /// Restart the daemon service
+    Restart,
     /// Install as a system service (systemd/launchd)
     Install,
     /// Uninstall the system service
     Uninstall,
     /// Check if the daemon is running
     Status,
+    /// Show daemon logs
+    Logs {
+        /// Follow the log output
+        #[arg(short, long)]
+        follow: bool,
+    },
 }
 
 fn main() -> Result<()> {
@@ -75,13 +88,17 @@ fn main() -> Result<()> {
 fn run_correction(auto_execute: bool, dry_run: bool) -> Result<()> {
     let config = config::load_config()?;
     let auto_execute = auto_execute || config.auto_execute.unwrap_or(false);
+    let sampling_params = config.sampling_params();
 
     let context = shell::read_command_context()?;
     let formatted = prompt::format_prompt(&context);
-    let fixes = model::infer(&formatted)?;
+    let fixes = model::infer(&formatted, &sampling_params)?;
 
     if fixes.is_empty() {
-        eprintln!("shit: can't figure this one out");
+        eprintln!(
+            "shit: can't figure this one out (last command exited {})",
+            context.current().exit_code
+        );
         return Ok(());
     }
 
@@ -95,28 +112,49 @@ fn run_correction(auto_execute: bool, dry_run: bool) -> Result<()> {
             wait_for_enter()?;
         }
         fixes[0].clone()
-    } else {
+    } else if dry_run {
         for (i, fix) in fixes.iter().enumerate() {
             eprintln!("  {} {}", i + 1, fix);
         }
-        if dry_run {
-            return Ok(());
-        }
-        if auto_execute {
-            fixes[0].clone()
+        return Ok(());
+    } else if auto_execute {
+        fixes[0].clone()
+    } else {
+        use std::io::IsTerminal;
+
+        let chosen = if std::io::stdout().is_terminal() {
+            selector::select(&fixes)?
         } else {
-            eprint!("  [1-{}]: ", fixes.len());
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input)?;
-            let idx: usize = input.trim().parse().unwrap_or(1);
-            let idx = idx.saturating_sub(1).min(fixes.len() - 1);
-            fixes[idx].clone()
+            None
+        };
+
+        match chosen {
+            Some(idx) => fixes[idx].clone(),
+            None if std::io::stdout().is_terminal() => {
+                eprintln!("shit: cancelled");
+                return Ok(());
+            }
+            None => prompt_numeric(&fixes)?,
         }
     };
 
     execute_command(&chosen)
 }
 
+/// Plain numbered prompt, used when stdout isn't a TTY (e.g. shell
+/// integration piping output) so non-interactive callers keep working.
+fn prompt_numeric(fixes: &[String]) -> Result<String> {
+    for (i, fix) in fixes.iter().enumerate() {
+        eprintln!("  {} {}", i + 1, fix);
+    }
+    eprint!("  [1-{}]: ", fixes.len());
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let idx: usize = input.trim().parse().unwrap_or(1);
+    let idx = idx.saturating_sub(1).min(fixes.len() - 1);
+    Ok(fixes[idx].clone())
+}
+
 fn wait_for_enter() -> Result<()> {
     use crossterm::event::{self, Event, KeyCode, KeyModifiers};
     use crossterm::terminal;
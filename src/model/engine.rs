@@ -1,11 +1,42 @@
 use anyhow::{bail, Result};
 use candle_core::{DType, Device, Tensor};
-use candle_transformers::generation::LogitsProcessor;
+use candle_transformers::generation::{LogitsProcessor, Sampling};
 use candle_transformers::models::quantized_gemma3::ModelWeights;
+use std::collections::HashSet;
 use std::path::Path;
 use tokenizers::Tokenizer;
 
-const MAX_GENERATED_TOKENS: usize = 100;
+/// Parameters controlling how `generate_tokens` samples each new token, how
+/// many tokens a single completion may run to, and how many independent
+/// completions to draw. `Default` reproduces the behavior this module had
+/// before sampling became configurable: greedy decoding, no repeat penalty,
+/// one sample with `max_tokens` of 100.
+#[derive(Clone, Debug)]
+pub struct SamplingParams {
+    pub temperature: Option<f64>,
+    pub top_k: Option<usize>,
+    pub top_p: Option<f64>,
+    pub repeat_penalty: f32,
+    pub repeat_last_n: usize,
+    pub seed: u64,
+    pub max_tokens: usize,
+    pub num_samples: usize,
+}
+
+impl Default for SamplingParams {
+    fn default() -> Self {
+        Self {
+            temperature: None,
+            top_k: None,
+            top_p: None,
+            repeat_penalty: 1.0,
+            repeat_last_n: 64,
+            seed: 0,
+            max_tokens: 100,
+            num_samples: 1,
+        }
+    }
+}
 
 pub struct Engine {
     model: ModelWeights,
@@ -28,7 +59,28 @@ impl Engine {
         })
     }
 
-    pub fn infer(&mut self, prompt: &str) -> Result<String> {
+    /// Draw `params.num_samples` independent completions, each its own
+    /// greedy/sampled decode seeded off `params.seed` (offset by sample
+    /// index so samples don't collide when sampling is non-deterministic).
+    pub fn infer(&mut self, prompt: &str, params: &SamplingParams) -> Result<Vec<String>> {
+        (0..params.num_samples.max(1))
+            .map(|i| {
+                let mut sample_params = params.clone();
+                sample_params.seed = params.seed.wrapping_add(i as u64);
+                self.infer_streaming(prompt, &sample_params, &mut |_| Ok(()))
+            })
+            .collect()
+    }
+
+    /// Like `infer`, but draws a single completion and invokes `on_token`
+    /// with each newly decoded fragment as it is produced, instead of only
+    /// returning once generation is done.
+    pub fn infer_streaming(
+        &mut self,
+        prompt: &str,
+        params: &SamplingParams,
+        on_token: &mut dyn FnMut(&str) -> Result<()>,
+    ) -> Result<String> {
         let encoding = self
             .tokenizer
             .encode(prompt, true)
@@ -39,50 +91,154 @@ impl Engine {
         let model = &mut self.model;
         let tokenizer = &self.tokenizer;
 
-        generate_tokens(&prompt_tokens, tokenizer, &mut |tokens, pos| {
-            let input = Tensor::new(tokens, device)?.unsqueeze(0)?;
-            let logits = model.forward(&input, pos)?;
-            Ok(logits.squeeze(0)?)
-        })
+        generate_tokens(
+            &prompt_tokens,
+            tokenizer,
+            &mut |tokens, pos| {
+                let input = Tensor::new(tokens, device)?.unsqueeze(0)?;
+                let logits = model.forward(&input, pos)?;
+                Ok(logits.squeeze(0)?)
+            },
+            on_token,
+            params,
+        )
     }
 }
 
-/// Generate tokens from a model with greedy decoding.
+/// Generate tokens from a model, reporting each newly decoded fragment to
+/// `on_token` as it becomes available.
 fn generate_tokens(
     prompt_tokens: &[u32],
     tokenizer: &Tokenizer,
     forward_fn: &mut dyn FnMut(&[u32], usize) -> Result<Tensor>,
+    on_token: &mut dyn FnMut(&str) -> Result<()>,
+    params: &SamplingParams,
 ) -> Result<String> {
-    let mut logits_processor = LogitsProcessor::new(0, None, None);
+    let mut logits_processor = build_logits_processor(params);
 
     let logits = forward_fn(prompt_tokens, 0)?;
-    let logits = logits.to_dtype(DType::F32)?;
-    let mut next_logits = extract_last_logits(&logits)?;
-    let mut next_token = logits_processor.sample(&next_logits)?;
+    let logits = extract_last_logits(&logits.to_dtype(DType::F32)?)?;
+    let logits = apply_repeat_penalty(&logits, params, prompt_tokens, &[])?;
+    let mut next_token = logits_processor.sample(&logits)?;
 
     let mut generated_tokens = vec![next_token];
     let mut pos = prompt_tokens.len();
+    let mut token_anchor = 0usize;
+
+    emit_new_fragment(tokenizer, &generated_tokens, &mut token_anchor, on_token)?;
 
-    for _ in 0..MAX_GENERATED_TOKENS {
+    for _ in 0..params.max_tokens {
         let logits = forward_fn(&[next_token], pos)?;
-        let logits = logits.to_dtype(DType::F32)?;
-        next_logits = extract_last_logits(&logits)?;
-        next_token = logits_processor.sample(&next_logits)?;
+        let logits = extract_last_logits(&logits.to_dtype(DType::F32)?)?;
+        let logits = apply_repeat_penalty(&logits, params, prompt_tokens, &generated_tokens)?;
+        next_token = logits_processor.sample(&logits)?;
 
         if next_token == 1 || next_token == 0 {
             break;
         }
         generated_tokens.push(next_token);
         pos += 1;
+
+        emit_new_fragment(tokenizer, &generated_tokens, &mut token_anchor, on_token)?;
     }
 
-    let output = tokenizer
+    let full_text = tokenizer
         .decode(&generated_tokens, true)
-        .map_err(anyhow::Error::msg)?
-        .trim()
-        .to_string();
+        .map_err(anyhow::Error::msg)?;
+    Ok(full_text.trim().to_string())
+}
+
+/// Picks the `candle_transformers` sampling strategy matching whichever of
+/// `temperature`/`top_k`/`top_p` are set. No temperature means plain
+/// argmax (the original greedy-decoding behavior), regardless of the other
+/// two fields.
+fn build_logits_processor(params: &SamplingParams) -> LogitsProcessor {
+    let sampling = match (params.temperature, params.top_k, params.top_p) {
+        (None, _, _) => Sampling::ArgMax,
+        (Some(temperature), Some(k), Some(p)) => Sampling::TopKThenTopP { k, p, temperature },
+        (Some(temperature), Some(k), None) => Sampling::TopK { k, temperature },
+        (Some(temperature), None, Some(p)) => Sampling::TopP { p, temperature },
+        (Some(temperature), None, None) => Sampling::All { temperature },
+    };
+    LogitsProcessor::from_sampling(params.seed, sampling)
+}
+
+/// Penalize each token seen in the trailing `repeat_last_n` positions of
+/// `prompt_tokens` + `generated_tokens`: a positive logit is divided by
+/// `repeat_penalty` and a negative one multiplied by it, so either way the
+/// model is discouraged from repeating itself. A penalty of `1.0` (the
+/// default) is a no-op, so callers that don't care about repetition skip
+/// the cost entirely.
+fn apply_repeat_penalty(
+    logits: &Tensor,
+    params: &SamplingParams,
+    prompt_tokens: &[u32],
+    generated_tokens: &[u32],
+) -> Result<Tensor> {
+    if params.repeat_penalty == 1.0 {
+        return Ok(logits.clone());
+    }
+
+    let device = logits.device().clone();
+    let mut values = logits.to_vec1::<f32>()?;
+
+    let context: HashSet<u32> = prompt_tokens
+        .iter()
+        .chain(generated_tokens.iter())
+        .rev()
+        .take(params.repeat_last_n)
+        .copied()
+        .collect();
+
+    for token_id in context {
+        if let Some(value) = values.get_mut(token_id as usize) {
+            *value = if *value >= 0.0 {
+                *value / params.repeat_penalty
+            } else {
+                *value * params.repeat_penalty
+            };
+        }
+    }
+
+    let len = values.len();
+    Ok(Tensor::from_vec(values, len, &device)?)
+}
+
+/// Decode the unconfirmed token window (`generated_tokens[*token_anchor..]`)
+/// twice — once without the latest token ("prefix") and once with it
+/// ("full") — and emit only the suffix the latest token added. Re-decoding a
+/// short window rather than diffing against a running total keeps
+/// multi-token subwords intact, and comparing two fresh decodes of the same
+/// window (instead of trusting an old decode to still be a byte-exact
+/// prefix of a new one) tolerates SentencePiece/BPE boundary shifts, e.g.
+/// leading-space (`▁`) merges changing how an earlier token in the window
+/// renders once a later one joins it. When `full` isn't (yet) longer than
+/// `prefix`, or `prefix.len()` isn't a char boundary of `full`, the anchor
+/// doesn't advance and the next token gets another chance to resolve the
+/// window — mirroring candle's own `TokenOutputStream` helper. Whether
+/// `full` actually starts with `prefix` is not checked.
+fn emit_new_fragment(
+    tokenizer: &Tokenizer,
+    generated_tokens: &[u32],
+    token_anchor: &mut usize,
+    on_token: &mut dyn FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let window = &generated_tokens[*token_anchor..];
+    if window.is_empty() {
+        return Ok(());
+    }
+
+    let prefix = tokenizer
+        .decode(&window[..window.len() - 1], true)
+        .map_err(anyhow::Error::msg)?;
+    let full = tokenizer.decode(window, true).map_err(anyhow::Error::msg)?;
+
+    if full.len() > prefix.len() && full.is_char_boundary(prefix.len()) {
+        on_token(&full[prefix.len()..])?;
+        *token_anchor = generated_tokens.len();
+    }
 
-    Ok(output)
+    Ok(())
 }
 
 /// Extract the last position's logits from a tensor of varying shape.
@@ -1,13 +1,20 @@
 use anyhow::{bail, Result};
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-use super::engine::Engine;
+use super::engine::{Engine, SamplingParams};
 
 const GITHUB_REPO: &str = env!("GITHUB_REPO");
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const MODEL_SHA256: &str = env!("MODEL_SHA256");
 const TOKENIZER_SHA256: &str = env!("TOKENIZER_SHA256");
 
+/// The crate version and expected model/tokenizer SHA256 baked in at build
+/// time, exposed for the daemon's `status` RPC method.
+pub fn model_info() -> (&'static str, &'static str, &'static str) {
+    (VERSION, MODEL_SHA256, TOKENIZER_SHA256)
+}
+
 pub struct ModelPaths {
     pub model_path: PathBuf,
     pub tokenizer_path: PathBuf,
@@ -76,6 +83,18 @@ pub fn find_model() -> Result<ModelPaths> {
     })
 }
 
+/// Content-addressable blob store directory (`<cache_dir>/shit/blobs`).
+/// Blobs are named by their verified SHA256, so switching model versions
+/// never evicts a still-valid download, and re-downloads of the same build
+/// dedupe for free.
+fn blob_store_dir() -> Result<PathBuf> {
+    let cache_dir =
+        dirs::cache_dir().ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
+    let dir = cache_dir.join("shit").join("blobs");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
 fn download_file_with_fallback(
     url: &str,
     fallback_url: &str,
@@ -96,13 +115,54 @@ fn download_file_with_fallback(
     }
 }
 
+/// Ensure the blob for `expected_sha256` exists in the content-addressable
+/// store (fetching/resuming it from `url` if not), then link or copy it to
+/// `dest`.
 fn download_file(url: &str, dest: &PathBuf, expected_sha256: &str) -> Result<()> {
+    let blob_path = blob_store_dir()?.join(expected_sha256);
+    let display_name = dest.file_name().unwrap().to_string_lossy().to_string();
+
+    if !blob_path.exists() {
+        fetch_blob(url, &blob_path, expected_sha256, &display_name)?;
+    }
+
+    if dest.exists() {
+        std::fs::remove_file(dest)?;
+    }
+    // Prefer a hard link (no extra disk use); fall back to a copy when the
+    // cache and destination live on different filesystems.
+    if std::fs::hard_link(&blob_path, dest).is_err() {
+        std::fs::copy(&blob_path, dest)?;
+    }
+
+    Ok(())
+}
+
+/// Download `url` into `blob_path`, resuming from an existing `.part` file
+/// via HTTP Range if one is present. Since the SHA256 hasher can't be
+/// rewound, a resume re-hashes the bytes already on disk before continuing
+/// to hash newly-received ones.
+fn fetch_blob(url: &str, blob_path: &Path, expected_sha256: &str, display_name: &str) -> Result<()> {
     use sha2::{Digest, Sha256};
     use std::io::{Read, Write};
     use std::time::Duration;
 
-    let filename = dest.file_name().unwrap().to_string_lossy();
-    eprint!("shit: downloading {}...", filename);
+    eprint!("shit: downloading {}...", display_name);
+
+    let tmp = blob_path.with_extension("part");
+    let mut hasher = Sha256::new();
+    let mut resume_from: u64 = 0;
+    if let Ok(mut existing) = std::fs::File::open(&tmp) {
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let n = existing.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            resume_from += n as u64;
+        }
+    }
 
     let agent = ureq::Agent::config_builder()
         .timeout_connect(Some(Duration::from_secs(10)))
@@ -110,20 +170,39 @@ fn download_file(url: &str, dest: &PathBuf, expected_sha256: &str) -> Result<()>
         .build()
         .new_agent();
 
-    let response = agent.get(url).call()?;
+    let mut req = agent.get(url);
+    if resume_from > 0 {
+        req = req.header("Range", format!("bytes={}-", resume_from));
+    }
+    let response = req.call()?;
+
+    // A 206 means the server honored our Range request; a 200 means it was
+    // ignored (e.g. no Range support), so we must truncate and start over.
+    let resumed = resume_from > 0 && response.status() == 206;
+    let mut downloaded = if resumed {
+        resume_from
+    } else {
+        hasher = Sha256::new();
+        0
+    };
+
     let total: Option<u64> = response
         .headers()
         .get("content-length")
         .and_then(|v| v.to_str().ok())
-        .and_then(|v| v.parse().ok());
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(|len| len + downloaded);
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&tmp)?;
 
     let mut reader = response.into_body().into_reader();
-    let tmp = dest.with_extension("part");
-    let mut file = std::fs::File::create(&tmp)?;
-    let mut hasher = Sha256::new();
-    let mut downloaded: u64 = 0;
     let mut buf = [0u8; 64 * 1024];
-    let mut last_report = 0u64;
+    let mut last_report = downloaded;
 
     loop {
         let n = reader.read(&mut buf)?;
@@ -138,7 +217,7 @@ fn download_file(url: &str, dest: &PathBuf, expected_sha256: &str) -> Result<()>
             if let Some(total) = total {
                 eprint!(
                     "\rshit: downloading {}... {}/{}MB",
-                    filename,
+                    display_name,
                     downloaded / 1_000_000,
                     total / 1_000_000
                 );
@@ -154,14 +233,14 @@ fn download_file(url: &str, dest: &PathBuf, expected_sha256: &str) -> Result<()>
         let _ = std::fs::remove_file(&tmp);
         bail!(
             "SHA256 mismatch for {}: expected {}, got {}",
-            filename,
+            display_name,
             expected_sha256,
             actual_hash
         );
     }
 
-    std::fs::rename(&tmp, dest)?;
-    eprintln!("\rshit: downloaded {}              ", filename);
+    std::fs::rename(&tmp, blob_path)?;
+    eprintln!("\rshit: downloaded {}              ", display_name);
     Ok(())
 }
 
@@ -194,28 +273,179 @@ fn apply_op(command: &str, op: &str) -> Option<String> {
     }
 }
 
+/// Expand the model's output into ranked fix candidates. The model may
+/// propose several ops for the same command, one per line, in descending
+/// order of confidence; each line that applies becomes a candidate, in that
+/// same order, for the interactive selector to present.
 fn infer_from_op(prompt: &str, op: &str) -> Vec<String> {
+    // The command currently being corrected is the *last* "$ " line in the
+    // prompt — earlier ones are preceding history kept only as context.
     let command = prompt
         .lines()
-        .find_map(|line| line.strip_prefix("$ "))
+        .filter_map(|line| line.strip_prefix("$ "))
+        .last()
         .unwrap_or("");
 
-    let op = op.trim();
-    if op == "NONE" || op.is_empty() {
-        return vec![];
+    op.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && *line != "NONE")
+        .filter_map(|line| apply_op(command, line))
+        .collect()
+}
+
+/// Expand every op string drawn (one per `SamplingParams::num_samples`) into
+/// fix candidates and flatten them into a single ranked list, preserving the
+/// order samples were produced in and dropping exact duplicates a later
+/// sample happens to repeat.
+fn fixes_from_ops(prompt: &str, ops: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    ops.iter()
+        .flat_map(|op| infer_from_op(prompt, op))
+        .filter(|fix| seen.insert(fix.clone()))
+        .collect()
+}
+
+/// Sampling parameters as sent over the wire to the daemon. Every field is
+/// always sent; an unset `temperature`/`top_k`/`top_p` goes out as `null`,
+/// which the daemon treats as absent and fills from its own `config.toml`.
+fn sampling_params_json(params: &SamplingParams) -> serde_json::Value {
+    serde_json::json!({
+        "temperature": params.temperature,
+        "top_k": params.top_k,
+        "top_p": params.top_p,
+        "repeat_penalty": params.repeat_penalty,
+        "repeat_last_n": params.repeat_last_n,
+        "seed": params.seed,
+        "max_tokens": params.max_tokens,
+        "num_samples": params.num_samples,
+    })
+}
+
+/// Try the Unix-domain-socket gateway, which is trusted purely by its
+/// `0600` filesystem permissions, so no capability token is needed. This is
+/// the default/preferred local transport on Linux/macOS, and it streams
+/// tokens as they're produced (one `{"token": "..."}` line per fragment,
+/// then a final `{"fixes": [...]}`/`{"error": "..."}` line) so preferring it
+/// over `try_daemon_streaming` doesn't cost responsiveness.
+#[cfg(all(feature = "daemon", unix))]
+fn try_daemon_unix_socket(prompt: &str, params: &SamplingParams) -> Option<Vec<String>> {
+    use std::io::{BufRead, Write};
+    use std::os::unix::net::UnixStream;
+
+    let socket_path = crate::daemon::server::socket_path();
+    let stream = UnixStream::connect(&socket_path).ok()?;
+    stream
+        .set_read_timeout(Some(std::time::Duration::from_secs(30)))
+        .ok()?;
+
+    let mut writer = stream.try_clone().ok()?;
+    let mut body = sampling_params_json(params);
+    body["prompt"] = serde_json::json!(prompt);
+    writeln!(writer, "{}", body).ok()?;
+
+    let mut reader = std::io::BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+
+        let v: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+        if let Some(token) = v["token"].as_str() {
+            eprint!("{token}");
+            continue;
+        }
+        if let Some(fixes) = v["fixes"].as_array() {
+            eprintln!();
+            return Some(
+                fixes
+                    .iter()
+                    .filter_map(|f| f.as_str().map(|s| s.to_string()))
+                    .collect(),
+            );
+        }
+        if let Some(error) = v["error"].as_str() {
+            eprintln!("\nshit: daemon inference failed: {error}");
+            return Some(vec![]);
+        }
+        return None;
     }
-    if let Some(fix) = apply_op(command, op) {
-        return vec![fix];
+}
+
+#[cfg(all(feature = "daemon", not(unix)))]
+fn try_daemon_unix_socket(_prompt: &str, _params: &SamplingParams) -> Option<Vec<String>> {
+    None
+}
+
+/// Try the daemon's streaming endpoint first, printing fragments as they
+/// arrive. Returns None (falling back to the buffered `try_daemon` path) if
+/// the daemon isn't reachable or doesn't support streaming.
+#[cfg(feature = "daemon")]
+fn try_daemon_streaming(prompt: &str, params: &SamplingParams) -> Option<Vec<String>> {
+    use std::io::BufRead;
+    use std::time::Duration;
+
+    let port_file = crate::daemon::server::port_file_path();
+    if !port_file.exists() {
+        return None; // no daemon installed, silent fallback
     }
-    if op.starts_with("FULL ") {
-        return vec![op[5..].to_string()];
+
+    let (port, token) = crate::daemon::server::read_port_file(&port_file).ok()?;
+
+    let url = format!("http://127.0.0.1:{}/infer/stream", port);
+    let mut body = sampling_params_json(params);
+    body["prompt"] = serde_json::json!(prompt);
+    let body = body.to_string();
+
+    let agent = ureq::Agent::config_builder()
+        .timeout_connect(Some(Duration::from_secs(2)))
+        .timeout_global(Some(Duration::from_secs(30)))
+        .build()
+        .new_agent();
+
+    let response = match agent
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("X-Shit-Token", &token)
+        .send(body.as_str())
+    {
+        Ok(response) => response,
+        Err(_) => return None,
+    };
+
+    let reader = std::io::BufReader::new(response.into_body().into_reader());
+    for line in reader.lines() {
+        let line = line.ok()?;
+        if line.is_empty() {
+            continue;
+        }
+        let v: serde_json::Value = serde_json::from_str(&line).ok()?;
+        if let Some(token) = v["token"].as_str() {
+            eprint!("{token}");
+            continue;
+        }
+        if let Some(fixes) = v["fixes"].as_array() {
+            eprintln!();
+            return Some(
+                fixes
+                    .iter()
+                    .filter_map(|f| f.as_str().map(|s| s.to_string()))
+                    .collect(),
+            );
+        }
+        if let Some(error) = v["error"].as_str() {
+            eprintln!("\nshit: daemon inference failed: {error}");
+            return Some(vec![]);
+        }
     }
-    vec![]
+
+    None
 }
 
 /// Try the daemon for inference. Returns Some(fixes) on success, None on failure.
 #[cfg(feature = "daemon")]
-fn try_daemon(prompt: &str) -> Option<Vec<String>> {
+fn try_daemon(prompt: &str, params: &SamplingParams) -> Option<Vec<String>> {
     use std::time::Duration;
 
     let port_file = crate::daemon::server::port_file_path();
@@ -223,11 +453,12 @@ fn try_daemon(prompt: &str) -> Option<Vec<String>> {
         return None; // no daemon installed, silent fallback
     }
 
-    let port_str = std::fs::read_to_string(&port_file).ok()?;
-    let port: u16 = port_str.trim().parse().ok()?;
+    let (port, token) = crate::daemon::server::read_port_file(&port_file).ok()?;
 
     let url = format!("http://127.0.0.1:{}/infer", port);
-    let body = serde_json::json!({"prompt": prompt}).to_string();
+    let mut body = sampling_params_json(params);
+    body["prompt"] = serde_json::json!(prompt);
+    let body = body.to_string();
 
     let agent = ureq::Agent::config_builder()
         .timeout_connect(Some(Duration::from_secs(2)))
@@ -237,6 +468,7 @@ fn try_daemon(prompt: &str) -> Option<Vec<String>> {
     match agent
         .post(&url)
         .header("Content-Type", "application/json")
+        .header("X-Shit-Token", &token)
         .send(body.as_str())
     {
         Ok(response) => {
@@ -257,29 +489,53 @@ fn try_daemon(prompt: &str) -> Option<Vec<String>> {
 }
 
 /// Run inference and return suggested fixes.
-pub fn infer(prompt: &str) -> Result<Vec<String>> {
-    // Try daemon first if feature enabled
+pub fn infer(prompt: &str, params: &SamplingParams) -> Result<Vec<String>> {
+    // Try the daemon first if the feature is enabled: the Unix socket gateway
+    // is preferred (filesystem-permission gated, no token needed, and itself
+    // streams tokens), falling back to the streaming TCP endpoint and then
+    // the buffered one on platforms/setups without the socket.
     #[cfg(feature = "daemon")]
-    if let Some(fixes) = try_daemon(prompt) {
+    if let Some(fixes) = try_daemon_unix_socket(prompt, params)
+        .or_else(|| try_daemon_streaming(prompt, params))
+        .or_else(|| try_daemon(prompt, params))
+    {
         return Ok(fixes);
     }
 
     // Fallback: load model locally
     let paths = find_model()?;
     let mut engine = Engine::new(&paths.model_path, &paths.tokenizer_path)?;
-    let op = engine.infer(prompt)?;
-    Ok(infer_from_op(prompt, &op))
+    let ops = engine.infer(prompt, params)?;
+    Ok(fixes_from_ops(prompt, &ops))
 }
 
 /// Run inference using an existing engine and return suggested fixes.
-pub fn infer_with_engine(engine: &mut Engine, prompt: &str) -> Result<Vec<String>> {
-    let op = engine.infer(prompt)?;
+pub fn infer_with_engine(
+    engine: &mut Engine,
+    prompt: &str,
+    params: &SamplingParams,
+) -> Result<Vec<String>> {
+    let ops = engine.infer(prompt, params)?;
+    Ok(fixes_from_ops(prompt, &ops))
+}
+
+/// Like `infer_with_engine`, but draws a single sample and invokes
+/// `on_fragment` with each newly decoded piece of the op as it is produced.
+/// `params.num_samples` is ignored here — streaming is inherently a
+/// single-completion UX.
+pub fn infer_with_engine_streaming(
+    engine: &mut Engine,
+    prompt: &str,
+    params: &SamplingParams,
+    on_fragment: &mut dyn FnMut(&str) -> Result<()>,
+) -> Result<Vec<String>> {
+    let op = engine.infer_streaming(prompt, params, on_fragment)?;
     Ok(infer_from_op(prompt, &op))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::apply_op;
+    use super::{apply_op, fixes_from_ops, infer_from_op};
 
     #[test]
     fn test_replace_op() {
@@ -329,4 +585,43 @@ mod tests {
     fn test_unknown_op() {
         assert_eq!(apply_op("git push", "UNKNOWN something"), None);
     }
+
+    #[test]
+    fn test_infer_from_op_ranks_multiple_candidates() {
+        let prompt = "$ git psuh origin main\n> git: 'psuh' is not a git command\nOP:";
+        let op = "REPLACE psuh push\nFLAG --force";
+        assert_eq!(
+            infer_from_op(prompt, op),
+            vec![
+                "git push origin main".to_string(),
+                "git --force psuh origin main".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_infer_from_op_uses_most_recent_history_entry() {
+        let prompt = "$ cd missing-dir\n> no such file or directory\n$ git psuh\n> git: 'psuh' is not a git command\nOP:";
+        let op = "REPLACE psuh push";
+        assert_eq!(
+            infer_from_op(prompt, op),
+            vec!["git push".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_fixes_from_ops_dedupes_across_samples() {
+        let prompt = "$ git psuh origin main\n> git: 'psuh' is not a git command\nOP:";
+        let ops = vec![
+            "REPLACE psuh push".to_string(),
+            "REPLACE psuh push\nFLAG --force".to_string(),
+        ];
+        assert_eq!(
+            fixes_from_ops(prompt, &ops),
+            vec![
+                "git push origin main".to_string(),
+                "git --force psuh origin main".to_string(),
+            ]
+        );
+    }
 }
@@ -1,5 +1,7 @@
 pub mod engine;
 mod inference;
 
-pub use engine::Engine;
-pub use inference::{find_model, infer, infer_with_engine};
+pub use engine::{Engine, SamplingParams};
+pub use inference::{
+    find_model, infer, infer_with_engine, infer_with_engine_streaming, model_info,
+};
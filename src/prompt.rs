@@ -1,10 +1,16 @@
 use crate::shell::CommandContext;
 
+/// Renders the command history into the model's prompt format, oldest
+/// command first, so a preceding `cd` (or similar) is available as context
+/// for the one actually being corrected.
 pub fn format_prompt(ctx: &CommandContext) -> String {
-    let mut prompt = format!("$ {}\n", ctx.command);
+    let mut prompt = String::new();
 
-    for line in ctx.stderr.lines() {
-        prompt.push_str(&format!("> {line}\n"));
+    for entry in &ctx.history {
+        prompt.push_str(&format!("$ {}\n", entry.command));
+        for line in entry.stderr.lines() {
+            prompt.push_str(&format!("> {line}\n"));
+        }
     }
 
     prompt.push_str("OP:");
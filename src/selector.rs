@@ -0,0 +1,190 @@
+//! Interactive, fuzzy-filterable list used to pick among several candidate
+//! fixes. Falls back to a plain numeric prompt when stdout isn't a TTY (see
+//! `main::run_correction`), so piping `shit` into other tools keeps working.
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::{cursor, queue, terminal};
+use std::io::Write;
+
+/// Drive an interactive fuzzy picker over `candidates` and return the index
+/// of the selected one, or `None` if the user cancelled with Ctrl-C/Esc.
+pub fn select(candidates: &[String]) -> Result<Option<usize>> {
+    let mut query = String::new();
+    let mut stderr = std::io::stderr();
+
+    terminal::enable_raw_mode()?;
+    let result = (|| -> Result<Option<usize>> {
+        let mut selected = 0usize;
+        loop {
+            let matches = filter(candidates, &query);
+            selected = selected.min(matches.len().saturating_sub(1));
+            render(&mut stderr, &query, candidates, &matches, selected)?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c')
+                {
+                    return Ok(None);
+                }
+                match key.code {
+                    KeyCode::Esc => return Ok(None),
+                    KeyCode::Enter => {
+                        return Ok(matches.get(selected).map(|&(idx, _)| idx));
+                    }
+                    KeyCode::Up => selected = selected.saturating_sub(1),
+                    KeyCode::Down => {
+                        if selected + 1 < matches.len() {
+                            selected += 1;
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        query.pop();
+                        selected = 0;
+                    }
+                    KeyCode::Char(c) => {
+                        query.push(c);
+                        selected = 0;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    })();
+    terminal::disable_raw_mode()?;
+
+    // Clear the rendered list so the chosen fix prints cleanly below it.
+    let _ = queue!(stderr, cursor::MoveToColumn(0), terminal::Clear(terminal::ClearType::FromCursorDown));
+    let _ = stderr.flush();
+
+    result
+}
+
+fn render(
+    out: &mut impl Write,
+    query: &str,
+    candidates: &[String],
+    matches: &[(usize, i64)],
+    selected: usize,
+) -> Result<()> {
+    queue!(
+        out,
+        cursor::MoveToColumn(0),
+        terminal::Clear(terminal::ClearType::FromCursorDown)
+    )?;
+    write!(out, "  filter: {query}\r\n")?;
+    for (row, &(idx, _)) in matches.iter().enumerate() {
+        let marker = if row == selected { ">" } else { " " };
+        write!(out, "{marker} {}\r\n", candidates[idx])?;
+    }
+    if matches.is_empty() {
+        write!(out, "  (no matches)\r\n")?;
+    }
+    let lines_written = matches.len().max(1) + 1;
+    queue!(out, cursor::MoveUp(lines_written as u16))?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Filter and rank `candidates` against `query`, returning `(original_index,
+/// score)` pairs sorted by descending score, stable on original order.
+fn filter(candidates: &[String], query: &str) -> Vec<(usize, i64)> {
+    if query.is_empty() {
+        return candidates.iter().enumerate().map(|(i, _)| (i, 0)).collect();
+    }
+
+    let mut scored: Vec<(usize, i64)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| fuzzy_score(query, c).map(|score| (i, score)))
+        .collect();
+    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    scored
+}
+
+/// Subsequence fuzzy-match score: every char of `query` must appear in `c`,
+/// in order, but not necessarily contiguously. Returns `None` if `query`
+/// isn't a subsequence of `c`. Higher scores rank better matches first.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    const BASE: i64 = 10;
+    const CONSECUTIVE_BONUS: i64 = 25;
+    const WORD_BOUNDARY_BONUS: i64 = 20;
+    const LEADING_GAP_PENALTY: i64 = 1;
+
+    let query = query.to_lowercase();
+    let candidate_lower = candidate.to_lowercase();
+    let c_chars: Vec<char> = candidate_lower.chars().collect();
+
+    let mut score = 0i64;
+    let mut c_idx = 0usize;
+    let mut prev_matched_idx: Option<usize> = None;
+
+    for q_char in query.chars() {
+        let found = c_chars[c_idx..].iter().position(|&ch| ch == q_char);
+        let Some(offset) = found else {
+            return None;
+        };
+        let match_idx = c_idx + offset;
+
+        score += BASE;
+
+        let is_consecutive = prev_matched_idx == Some(match_idx.wrapping_sub(1));
+        if is_consecutive {
+            score += CONSECUTIVE_BONUS;
+        }
+
+        let is_word_boundary = match_idx == 0
+            || matches!(c_chars[match_idx - 1], ' ' | '-' | '/');
+        if is_word_boundary {
+            score += WORD_BOUNDARY_BONUS;
+        }
+
+        if prev_matched_idx.is_none() {
+            score -= match_idx as i64 * LEADING_GAP_PENALTY;
+        }
+
+        prev_matched_idx = Some(match_idx);
+        c_idx = match_idx + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_score;
+
+    #[test]
+    fn subsequence_match_scores_and_non_match_is_filtered() {
+        let push = fuzzy_score("push", "git push origin main").unwrap();
+        let pull = fuzzy_score("push", "git pull origin main");
+        assert!(pull.is_none());
+        assert!(push > 0);
+    }
+
+    #[test]
+    fn requires_in_order_subsequence() {
+        assert!(fuzzy_score("gpsh", "git push").is_some());
+        assert!(fuzzy_score("hpgs", "git push").is_none());
+    }
+
+    #[test]
+    fn consecutive_matches_beat_scattered_ones() {
+        let consecutive = fuzzy_score("pus", "git push").unwrap();
+        let scattered = fuzzy_score("pus", "p u s h scattered").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn word_boundary_matches_score_higher() {
+        let boundary = fuzzy_score("p", "git push").unwrap();
+        let mid_word = fuzzy_score("p", "git uphill").unwrap();
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn empty_query_matches_nothing_to_filter_on() {
+        // empty query is handled by `filter`, not `fuzzy_score`, but the
+        // scorer itself should still treat it as a trivial match.
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+}
@@ -0,0 +1,57 @@
+/// Bash integration: a `PROMPT_COMMAND` hook that, on a failed command,
+/// appends a `command\nexit_code\nstderr` entry (record-separator delimited,
+/// see `super::HISTORY_DELIMITER`) to `/tmp/shit-$USER-last` and trims the
+/// file to the last `super::HISTORY_LIMIT` entries. Stderr is captured by
+/// tee-ing it to a side log for the whole session and slicing off whatever
+/// arrived since the previous prompt, since there's no way to recover a
+/// command's stderr after the fact otherwise.
+pub fn init_script() -> &'static str {
+    r#"
+__shit_stderr_log="/tmp/shit-$(id -un)-stderr"
+__shit_last_file="/tmp/shit-$(id -un)-last"
+__shit_stderr_offset=0
+: > "$__shit_stderr_log"
+exec 2> >(tee -a "$__shit_stderr_log" >&2)
+
+__shit_trim_history() {
+    local limit=5
+    [ -f "$__shit_last_file" ] || return
+    local tmp
+    tmp=$(mktemp)
+    awk -v RS=$'\x1e' -v limit="$limit" '
+        { entries[NR] = $0 }
+        END {
+            start = NR - limit + 1
+            if (start < 1) start = 1
+            for (i = start; i <= NR; i++) {
+                if (length(entries[i]) > 0) printf "%s%s", entries[i], RS
+            }
+        }
+    ' "$__shit_last_file" > "$tmp"
+    mv "$tmp" "$__shit_last_file"
+}
+
+__shit_precmd() {
+    local exit_code=$?
+    local last_command
+    last_command=$(HISTTIMEFORMAT= history 1 | sed 's/^[ ]*[0-9]*[ ]*//')
+
+    local size
+    size=$(wc -c < "$__shit_stderr_log" 2>/dev/null || echo 0)
+
+    if [ "$exit_code" -ne 0 ] && [ -n "$last_command" ]; then
+        local stderr_chunk=""
+        if [ "$size" -gt "$__shit_stderr_offset" ]; then
+            stderr_chunk=$(tail -c +"$((__shit_stderr_offset + 1))" "$__shit_stderr_log")
+        fi
+
+        { printf '%s\n%s\n%s' "$last_command" "$exit_code" "$stderr_chunk"; printf '\x1e'; } >> "$__shit_last_file"
+        __shit_trim_history
+    fi
+
+    __shit_stderr_offset=$size
+}
+
+PROMPT_COMMAND="__shit_precmd${PROMPT_COMMAND:+; $PROMPT_COMMAND}"
+"#
+}
@@ -0,0 +1,54 @@
+/// Fish integration: a `fish_postexec` event handler with the same
+/// ring-buffer-over-`tee` approach as the bash script (see `super::bash`).
+/// Fish has no `exec 2> >(...)` process substitution for output, so the
+/// stderr tee is wired through a named pipe instead.
+pub fn init_script() -> &'static str {
+    r#"
+set -g __shit_stderr_log "/tmp/shit-"(whoami)"-stderr"
+set -g __shit_last_file "/tmp/shit-"(whoami)"-last"
+set -g __shit_stderr_offset 0
+echo -n "" > $__shit_stderr_log
+
+set -g __shit_stderr_fifo (mktemp -u)
+mkfifo $__shit_stderr_fifo
+tee -a $__shit_stderr_log < $__shit_stderr_fifo >&2 &
+disown
+exec 2> $__shit_stderr_fifo
+
+function __shit_trim_history
+    set -l limit 5
+    test -f $__shit_last_file; or return
+    set -l tmp (mktemp)
+    awk -v RS='\x1e' -v limit=$limit '
+        { entries[NR] = $0 }
+        END {
+            start = NR - limit + 1
+            if (start < 1) start = 1
+            for (i = start; i <= NR; i++) {
+                if (length(entries[i]) > 0) printf "%s%s", entries[i], RS
+            }
+        }
+    ' $__shit_last_file > $tmp
+    mv $tmp $__shit_last_file
+end
+
+function __shit_postexec --on-event fish_postexec
+    set -l exit_code $status
+    set -l last_command $argv[1]
+
+    set -l size (wc -c < $__shit_stderr_log 2>/dev/null; or echo 0)
+
+    if test $exit_code -ne 0 -a -n "$last_command"
+        set -l stderr_chunk ""
+        if test $size -gt $__shit_stderr_offset
+            set stderr_chunk (tail -c +(math $__shit_stderr_offset + 1) $__shit_stderr_log)
+        end
+
+        printf '%s\n%s\n%s\x1e' "$last_command" "$exit_code" "$stderr_chunk" >> $__shit_last_file
+        __shit_trim_history
+    end
+
+    set -g __shit_stderr_offset $size
+end
+"#
+}
@@ -6,12 +6,40 @@ mod zsh;
 
 use anyhow::{bail, Result};
 
-pub struct CommandContext {
+/// A single failed command captured by the shell integration, with enough
+/// context for the model to propose a fix.
+pub struct FailedCommand {
     pub command: String,
     pub exit_code: i32,
     pub stderr: String,
 }
 
+/// A short ring buffer of recent failed commands, oldest first. The shell
+/// init scripts append one entry per failure and trim from the front once
+/// the buffer exceeds `HISTORY_LIMIT`, so a `cd` (or other setup command)
+/// that preceded the one actually being corrected is still visible to the
+/// model as context.
+pub struct CommandContext {
+    pub history: Vec<FailedCommand>,
+}
+
+impl CommandContext {
+    /// The command that triggered this correction — always the most recent
+    /// entry in `history`.
+    pub fn current(&self) -> &FailedCommand {
+        self.history
+            .last()
+            .expect("read_command_context never returns an empty history")
+    }
+}
+
+/// Maximum number of recent failed commands the shell integration retains.
+pub const HISTORY_LIMIT: usize = 5;
+
+/// Entries in the history file are separated by this sentinel rather than a
+/// blank line, since stderr output can itself contain blank lines.
+const HISTORY_DELIMITER: char = '\u{1e}'; // ASCII record separator
+
 pub fn get_init_script(shell: &str) -> Result<&'static str> {
     match shell {
         "fish" => Ok(fish::init_script()),
@@ -23,20 +51,39 @@ pub fn get_init_script(shell: &str) -> Result<&'static str> {
     }
 }
 
+/// Reads the ring buffer of recent failed commands written by the shell
+/// integration. Each entry is `command\nexit_code\nstderr...`, with entries
+/// joined by `HISTORY_DELIMITER`, oldest first.
 pub fn read_command_context() -> Result<CommandContext> {
     let username = std::env::var("USER").or_else(|_| std::env::var("USERNAME"))?;
     let path = format!("/tmp/shit-{username}-last");
-    let contents = std::fs::read_to_string(&path)
-        .map_err(|_| anyhow::anyhow!("no recent failed command found (is shell integration set up?)"))?;
+    let contents = std::fs::read_to_string(&path).map_err(|_| {
+        anyhow::anyhow!("no recent failed command found (is shell integration set up?)")
+    })?;
+
+    let history: Vec<FailedCommand> = contents
+        .split(HISTORY_DELIMITER)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(parse_entry)
+        .collect();
+
+    if history.is_empty() {
+        bail!("no recent failed command found (is shell integration set up?)");
+    }
 
-    let mut lines = contents.lines();
+    Ok(CommandContext { history })
+}
+
+fn parse_entry(entry: &str) -> FailedCommand {
+    let mut lines = entry.lines();
     let command = lines.next().unwrap_or("").to_string();
     let exit_code: i32 = lines.next().unwrap_or("1").parse().unwrap_or(1);
     let stderr: String = lines.collect::<Vec<_>>().join("\n");
 
-    Ok(CommandContext {
+    FailedCommand {
         command,
         exit_code,
         stderr,
-    })
+    }
 }
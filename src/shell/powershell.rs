@@ -0,0 +1,46 @@
+/// PowerShell integration: overrides the `prompt` function (PowerShell has
+/// no separate precmd hook) to append a `command\nexit_code\nstderr` entry
+/// to `$env:TEMP\shit-$env:USERNAME-last` and trim it to the last 5 entries.
+/// Stderr is approximated from `$Error`, since native-command stderr isn't
+/// otherwise addressable from inside `prompt`.
+pub fn init_script() -> &'static str {
+    r#"
+$global:ShitLastFile = Join-Path $env:TEMP "shit-$env:USERNAME-last"
+$global:ShitErrorOffset = 0
+
+function global:Shit-TrimHistory {
+    param($Path, $Limit = 5)
+    if (-not (Test-Path $Path)) { return }
+    $content = Get-Content -Raw $Path
+    if (-not $content) { return }
+    $entries = $content -split "`u{1e}" | Where-Object { $_.Trim() -ne "" }
+    $trimmed = $entries | Select-Object -Last $Limit
+    $joined = ($trimmed -join "`u{1e}") + "`u{1e}"
+    Set-Content -NoNewline -Path $Path -Value $joined
+}
+
+function global:prompt {
+    $exitCode = $LASTEXITCODE
+    $succeeded = $?
+    $lastCommand = (Get-History -Count 1).CommandLine
+
+    if ((-not $succeeded -or ($exitCode -and $exitCode -ne 0)) -and $lastCommand) {
+        $code = if ($exitCode) { $exitCode } else { 1 }
+        $newCount = $global:Error.Count - $global:ShitErrorOffset
+        $stderrChunk = ""
+        if ($newCount -gt 0) {
+            $stderrChunk = (($global:Error | Select-Object -First $newCount) | ForEach-Object { $_.ToString() }) -join "`n"
+        }
+        $global:ShitErrorOffset = $global:Error.Count
+
+        $entry = "$lastCommand`n$code`n$stderrChunk`u{1e}"
+        Add-Content -NoNewline -Path $global:ShitLastFile -Value $entry
+        Shit-TrimHistory -Path $global:ShitLastFile -Limit 5
+    } else {
+        $global:ShitErrorOffset = $global:Error.Count
+    }
+
+    "PS $($executionContext.SessionState.Path.CurrentLocation)$('>' * ($nestedPromptLevel + 1)) "
+}
+"#
+}
@@ -0,0 +1,13 @@
+/// tcsh integration: the special `precmd` alias, fired before each prompt,
+/// appends a `command\nexit_code` entry (no stderr — tcsh has no process
+/// substitution to tee it through, unlike the other shells) to
+/// `/tmp/shit-$USER-last` and trims to the last 5 entries.
+pub fn init_script() -> &'static str {
+    r#"
+setenv SHIT_LAST_FILE "/tmp/shit-`whoami`-last"
+
+alias __shit_trim_history 'awk -v RS="\x1e" -v limit=5 '"'"'{ entries[NR] = $0 } END { start = NR - limit + 1; if (start < 1) start = 1; for (i = start; i <= NR; i++) if (length(entries[i]) > 0) printf "%s%s", entries[i], RS }'"'"' $SHIT_LAST_FILE > /tmp/.shit-trim-$$ && mv /tmp/.shit-trim-$$ $SHIT_LAST_FILE'
+
+alias precmd 'set __shit_exit_code = $status ; if ( $__shit_exit_code != 0 ) ( printf "%s\n%s\n" "`history -h 1`" "$__shit_exit_code" ; printf "\x1e" ) >>& $SHIT_LAST_FILE && __shit_trim_history'
+"#
+}
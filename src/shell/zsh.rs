@@ -0,0 +1,54 @@
+/// Zsh integration: a `precmd` hook with the same ring-buffer-over-`tee`
+/// approach as the bash script (see `super::bash`), adapted to zsh's
+/// `add-zsh-hook`/`fc` idioms.
+pub fn init_script() -> &'static str {
+    r#"
+typeset -g __shit_stderr_log="/tmp/shit-$(id -un)-stderr"
+typeset -g __shit_last_file="/tmp/shit-$(id -un)-last"
+typeset -gi __shit_stderr_offset=0
+: > "$__shit_stderr_log"
+exec 2> >(tee -a "$__shit_stderr_log" >&2)
+
+__shit_trim_history() {
+    local limit=5
+    [[ -f "$__shit_last_file" ]] || return
+    local tmp
+    tmp=$(mktemp)
+    awk -v RS=$'\x1e' -v limit="$limit" '
+        { entries[NR] = $0 }
+        END {
+            start = NR - limit + 1
+            if (start < 1) start = 1
+            for (i = start; i <= NR; i++) {
+                if (length(entries[i]) > 0) printf "%s%s", entries[i], RS
+            }
+        }
+    ' "$__shit_last_file" > "$tmp"
+    mv "$tmp" "$__shit_last_file"
+}
+
+__shit_precmd() {
+    local exit_code=$?
+    local last_command
+    last_command=$(fc -ln -1)
+
+    local size
+    size=$(wc -c < "$__shit_stderr_log" 2>/dev/null || echo 0)
+
+    if [[ $exit_code -ne 0 && -n "$last_command" ]]; then
+        local stderr_chunk=""
+        if (( size > __shit_stderr_offset )); then
+            stderr_chunk=$(tail -c +$((__shit_stderr_offset + 1)) "$__shit_stderr_log")
+        fi
+
+        { printf '%s\n%s\n%s' "$last_command" "$exit_code" "$stderr_chunk"; printf '\x1e'; } >> "$__shit_last_file"
+        __shit_trim_history
+    fi
+
+    __shit_stderr_offset=$size
+}
+
+autoload -Uz add-zsh-hook
+add-zsh-hook precmd __shit_precmd
+"#
+}